@@ -0,0 +1,73 @@
+// Deep `Real` expressions at increasing precision. Every operation rounds its
+// enclosure outward to a dyadic grid, so the cost grows with depth and bits
+// rather than with the size of exact denominators:
+//
+//   cargo run --release --example real_bench
+use std::time::Instant;
+
+use peanorust::n::BinNat;
+use peanorust::r::Real;
+use peanorust::z::Rounding;
+
+type R = Real<BinNat>;
+type Case = (&'static str, fn() -> R);
+
+fn rat(s: &str) -> R {
+    Real::from_rat(s.parse().unwrap())
+}
+
+// sqrt(2 + sqrt(2 + ... + sqrt(2))), n roots deep; tends to 2.
+fn nested_sqrt(n: usize) -> R {
+    let two = rat("2");
+    (0..n).fold(two.clone(), |x, _| two.add(&x).sqrt())
+}
+
+// n steps of the logistic map x -> 15/4 x (1 - x) from sqrt(2) - 1. The map
+// is chaotic, so every step costs about one more bit of the starting point.
+fn logistic(n: usize) -> R {
+    let (r, one) = (rat("15/4"), rat("1"));
+    (0..n).fold(R::sqrt2().sub(&one), |x, _| r.mul(&x).mul(&one.sub(&x)))
+}
+
+// sum_{j=1..n} 1 / (j sqrt j): a wide expression rather than a deep one.
+fn sum_of_roots(n: usize) -> R {
+    (1..=n)
+        .map(|j| {
+            let j = rat(&j.to_string());
+            rat("1").div(&j.mul(&j.sqrt())).unwrap()
+        })
+        .sum()
+}
+
+// sum_{j=0..n} pi^j / j! by Horner's rule, with pi shared by every level.
+fn horner_exp_pi(n: usize) -> R {
+    let pi = R::pi();
+    (1..=n).rev().fold(rat("1"), |acc, j| {
+        rat("1").add(&acc.mul(&pi).div(&rat(&j.to_string())).unwrap())
+    })
+}
+
+fn main() {
+    let cases: [Case; 5] = [
+        ("nested sqrt x40", || nested_sqrt(40)),
+        ("logistic x60", || logistic(60)),
+        ("sum 1/j^1.5 x200", || sum_of_roots(200)),
+        ("horner e^pi x40", || horner_exp_pi(40)),
+        ("sin(exp(pi))", || R::pi().exp().sin()),
+    ];
+    for (name, build) in cases {
+        for bits in [32, 128, 512] {
+            // Build afresh so cached enclosures do not carry over.
+            let x = build();
+            let start = Instant::now();
+            let i = x.approx(bits);
+            println!(
+                "{:<18} {:>4} bits {:>10.2?}   {}",
+                name,
+                bits,
+                start.elapsed(),
+                i.lo.to_fixed_string(20, Rounding::Floor)
+            );
+        }
+    }
+}
@@ -1,34 +1,43 @@
 use std::fmt;
 
+use crate::n::{Nat, Natural};
+use crate::q::Rat;
 use crate::r::Real;
 
 // Complex numbers (C) built on top of our "Real" stage.
-#[derive(Clone, Debug)]
-pub struct Complex {
-    pub re: Real,
-    pub im: Real,
+#[derive(Clone)]
+pub struct Complex<N = Nat> {
+    pub re: Real<N>,
+    pub im: Real<N>,
 }
 
-impl Complex {
-    pub fn new(re: Real, im: Real) -> Complex {
+impl<N: Natural> Complex<N> {
+    pub fn new(re: Real<N>, im: Real<N>) -> Complex<N> {
         Complex { re, im }
     }
 
-    pub fn add(&self, other: &Complex) -> Complex {
+    pub fn add(&self, other: &Complex<N>) -> Complex<N> {
         Complex {
             re: self.re.add(&other.re),
             im: self.im.add(&other.im),
         }
     }
 
-    pub fn sub(&self, other: &Complex) -> Complex {
+    pub fn sub(&self, other: &Complex<N>) -> Complex<N> {
         Complex {
             re: self.re.sub(&other.re),
             im: self.im.sub(&other.im),
         }
     }
 
-    pub fn mul(&self, other: &Complex) -> Complex {
+    pub fn neg(&self) -> Complex<N> {
+        Complex {
+            re: self.re.neg(),
+            im: self.im.neg(),
+        }
+    }
+
+    pub fn mul(&self, other: &Complex<N>) -> Complex<N> {
         // (a+bi)(c+di) = (ac - bd) + (ad + bc)i
         let ac = self.re.mul(&other.re);
         let bd = self.im.mul(&other.im);
@@ -39,11 +48,71 @@ impl Complex {
             im: ad.add(&bc),
         }
     }
+
+    pub fn div(&self, other: &Complex<N>) -> Option<Complex<N>> {
+        // (a+bi)/(c+di) = ((ac + bd) + (bc - ad)i) / (c^2 + d^2)
+        // Same partiality as Real::div: a zero divisor refines forever.
+        let ac = self.re.mul(&other.re);
+        let bd = self.im.mul(&other.im);
+        let bc = self.im.mul(&other.re);
+        let ad = self.re.mul(&other.im);
+        let norm = other.re.mul(&other.re).add(&other.im.mul(&other.im));
+        Some(Complex {
+            re: ac.add(&bd).div(&norm)?,
+            im: bc.sub(&ad).div(&norm)?,
+        })
+    }
 }
 
-impl fmt::Display for Complex {
+// Operators, with the same policy as `Real`.
+forward_binop!([N: Natural] Complex<N>, Add::add, AddAssign::add_assign, |a, b| Complex::add(a, b));
+forward_binop!([N: Natural] Complex<N>, Sub::sub, SubAssign::sub_assign, |a, b| Complex::sub(a, b));
+forward_binop!([N: Natural] Complex<N>, Mul::mul, MulAssign::mul_assign, |a, b| Complex::mul(a, b));
+forward_binop!([N: Natural] Complex<N>, Div::div, DivAssign::div_assign, |a, b| {
+    Complex::div(a, b).expect("Complex::div always returns Some")
+});
+forward_neg!([N: Natural] Complex<N>, |a| Complex::neg(a));
+forward_sum_product!(
+    [N: Natural] Complex<N>,
+    zero = Complex::new(Real::from_rat(Rat::zero()), Real::from_rat(Rat::zero())),
+    one = Complex::new(Real::from_rat(Rat::one()), Real::from_rat(Rat::zero()))
+);
+
+impl<N: Natural> fmt::Debug for Complex<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Complex")
+            .field("re", &self.re)
+            .field("im", &self.im)
+            .finish()
+    }
+}
+
+impl<N: Natural> fmt::Display for Complex<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Keep it simple and readable for demo output.
         write!(f, "({} + {}i)", self.re, self.im)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::q::test_util::rat;
+
+    fn real(num: isize, den: usize) -> Real {
+        Real::from_rat(rat(num, den))
+    }
+
+    #[test]
+    fn complex_division_inverts_multiplication() {
+        // (1 + 2i) / (1 - i) = (-1 + 3i) / 2
+        let z = Complex::new(real(1, 1), real(2, 1));
+        let w = Complex::new(real(1, 1), real(-1, 1));
+        let q = &z / &w;
+        let (re, im) = (q.re.interval(8), q.im.interval(8));
+        let (want_re, want_im) = (real(-1, 2).interval(1).lo, real(3, 2).interval(1).lo);
+        assert!(re.lo <= want_re && want_re <= re.hi);
+        assert!(im.lo <= want_im && want_im <= im.hi);
+        assert_eq!(format!("{}", -(q * w)), "(-1 + -2i)");
+    }
+}
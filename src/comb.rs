@@ -0,0 +1,301 @@
+use crate::n::Natural;
+use crate::q::Rat;
+use crate::z::Int;
+
+// Combinatorial numbers, each computed from its defining recurrence using
+// nothing but `add`/`mul` on the naturals backend (and `Rat` arithmetic for
+// Bernoulli and harmonic numbers). These are reference implementations: they
+// favor the textbook recurrence over speed, so they double as worked examples
+// and as something to check faster formulas against.
+//
+// Indices (n, k) are plain `usize` counts; the values are naturals of any
+// backend, so `binomial::<BinNat>(100, 50)` works where `Nat` would not.
+
+// n! = n * (n-1)!, 0! = 1.
+pub fn factorial<N: Natural>(n: usize) -> N {
+    (1..=n).fold(N::one(), |acc, k| acc.mul(&N::from_usize(k)))
+}
+
+// x (x-1) ... (x-k+1): k factors counting down. 0 once a factor hits zero.
+pub fn falling_factorial<N: Natural>(x: &N, k: usize) -> N {
+    let mut acc = N::one();
+    let mut factor = x.clone();
+    for _ in 0..k {
+        acc = acc.mul(&factor);
+        match factor.sub(&N::one()) {
+            Some(f) => factor = f,
+            None => return N::zero(), // the factor we just used was 0
+        }
+    }
+    acc
+}
+
+// x (x+1) ... (x+k-1): k factors counting up.
+pub fn rising_factorial<N: Natural>(x: &N, k: usize) -> N {
+    let mut acc = N::one();
+    let mut factor = x.clone();
+    for _ in 0..k {
+        acc = acc.mul(&factor);
+        factor = factor.succ();
+    }
+    acc
+}
+
+// C(n, k) by Pascal's rule C(n, k) = C(n-1, k-1) + C(n-1, k), building the
+// triangle row by row (only the first k+1 entries of each row are needed).
+pub fn binomial<N: Natural>(n: usize, k: usize) -> N {
+    if k > n {
+        return N::zero();
+    }
+    let mut row = vec![N::zero(); k + 1];
+    row[0] = N::one();
+    for i in 1..=n {
+        // Update right to left so row[j - 1] is still last row's value.
+        for j in (1..=k.min(i)).rev() {
+            row[j] = row[j].add(&row[j - 1]);
+        }
+    }
+    row.swap_remove(k)
+}
+
+// (k1 + ... + km)! / (k1! ... km!), as a product of binomials:
+// choose the slots for group 1, then group 2 among what is left, and so on.
+pub fn multinomial<N: Natural>(ks: &[usize]) -> N {
+    let mut total = 0;
+    let mut acc = N::one();
+    for &k in ks {
+        total += k;
+        acc = acc.mul(&binomial(total, k));
+    }
+    acc
+}
+
+// F(0) = 0, F(1) = 1, F(n) = F(n-1) + F(n-2).
+pub fn fibonacci<N: Natural>(n: usize) -> N {
+    linear_recurrence(N::zero(), N::one(), n)
+}
+
+// L(0) = 2, L(1) = 1, L(n) = L(n-1) + L(n-2).
+pub fn lucas<N: Natural>(n: usize) -> N {
+    linear_recurrence(N::from_usize(2), N::one(), n)
+}
+
+fn linear_recurrence<N: Natural>(a0: N, a1: N, n: usize) -> N {
+    let (mut a, mut b) = (a0, a1);
+    for _ in 0..n {
+        let next = a.add(&b);
+        a = std::mem::replace(&mut b, next);
+    }
+    a
+}
+
+// C(0) = 1, C(n+1) = sum_{i=0..n} C(i) C(n-i).
+pub fn catalan<N: Natural>(n: usize) -> N {
+    let mut c: Vec<N> = vec![N::one()];
+    for m in 0..n {
+        let next = (0..=m).fold(N::zero(), |acc, i| acc.add(&c[i].mul(&c[m - i])));
+        c.push(next);
+    }
+    c.swap_remove(n)
+}
+
+// Unsigned Stirling numbers of the first kind c(n, k): permutations of n
+// elements with k cycles. c(n+1, k) = n c(n, k) + c(n, k-1).
+// The signed version is (-1)^(n-k) c(n, k).
+pub fn stirling1<N: Natural>(n: usize, k: usize) -> N {
+    stirling_table(n, k, |i, _| N::from_usize(i))
+}
+
+// Stirling numbers of the second kind S(n, k): partitions of n elements into
+// k nonempty blocks. S(n+1, k) = k S(n, k) + S(n, k-1).
+pub fn stirling2<N: Natural>(n: usize, k: usize) -> N {
+    stirling_table(n, k, |_, j| N::from_usize(j))
+}
+
+// Both Stirling triangles share the shape T(i+1, j) = w(i, j) T(i, j) + T(i, j-1).
+fn stirling_table<N: Natural>(n: usize, k: usize, weight: impl Fn(usize, usize) -> N) -> N {
+    if k > n {
+        return N::zero();
+    }
+    let mut row = vec![N::zero(); k + 1];
+    row[0] = N::one();
+    for i in 0..n {
+        for j in (0..=k.min(i + 1)).rev() {
+            let stay = weight(i, j).mul(&row[j]);
+            row[j] = if j == 0 { stay } else { stay.add(&row[j - 1]) };
+        }
+    }
+    row.swap_remove(k)
+}
+
+// Bell numbers B(n): all partitions of n elements. Built with the Bell
+// triangle: each row starts with the previous row's last entry, and each
+// entry is its left neighbour plus the entry above that neighbour.
+pub fn bell<N: Natural>(n: usize) -> N {
+    let mut row = vec![N::one()];
+    for _ in 0..n {
+        let mut next = vec![row.last().expect("nonempty").clone()];
+        for above in &row {
+            let v = next.last().expect("nonempty").add(above);
+            next.push(v);
+        }
+        row = next;
+    }
+    row.swap_remove(0)
+}
+
+// Bernoulli numbers as exact rationals, from
+//   sum_{k=0..m} C(m+1, k) B_k = 0   (m >= 1),  B_0 = 1,
+// which gives the convention B_1 = -1/2.
+pub fn bernoulli<N: Natural>(n: usize) -> Rat<N> {
+    let mut b: Vec<Rat<N>> = vec![Rat::one()];
+    for m in 1..=n {
+        let s = (0..m).fold(Rat::zero(), |acc, k| {
+            acc.add(&Rat::from_int(Int::from_nat(binomial(m + 1, k))).mul(&b[k]))
+        });
+        let scale = Rat::new(Int::from_nat(N::one()), N::from_usize(m + 1)).expect("m + 1 != 0");
+        b.push(s.mul(&scale).neg());
+    }
+    b.swap_remove(n)
+}
+
+// H(n) = 1 + 1/2 + ... + 1/n, H(0) = 0.
+pub fn harmonic<N: Natural>(n: usize) -> Rat<N> {
+    (1..=n).fold(Rat::zero(), |acc, k| {
+        acc.add(&Rat::new(Int::from_nat(N::one()), N::from_usize(k)).expect("k != 0"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::{BinNat, Nat};
+
+    fn u<N: Natural>(x: N) -> usize {
+        x.to_usize()
+    }
+
+    #[test]
+    fn small_values() {
+        assert_eq!(u(factorial::<Nat>(5)), 120);
+        // Cross-check against the primitive recursion on Peano naturals.
+        for n in 0..7 {
+            assert_eq!(factorial::<Nat>(n), Nat::from_usize(n).factorial_by_rec());
+        }
+        assert_eq!(u(falling_factorial(&Nat::from_usize(7), 3)), 210);
+        assert_eq!(u(falling_factorial(&Nat::from_usize(2), 5)), 0);
+        assert_eq!(u(rising_factorial(&Nat::from_usize(3), 3)), 60);
+        assert_eq!(u(binomial::<Nat>(6, 2)), 15);
+        assert_eq!(u(binomial::<Nat>(2, 6)), 0);
+        assert_eq!(u(multinomial::<Nat>(&[2, 1, 1])), 12);
+
+        let fib: Vec<usize> = (0..10).map(|n| u(fibonacci::<Nat>(n))).collect();
+        assert_eq!(fib, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+        let luc: Vec<usize> = (0..6).map(|n| u(lucas::<Nat>(n))).collect();
+        assert_eq!(luc, vec![2, 1, 3, 4, 7, 11]);
+        let cat: Vec<usize> = (0..7).map(|n| u(catalan::<Nat>(n))).collect();
+        assert_eq!(cat, vec![1, 1, 2, 5, 14, 42, 132]);
+        let bells: Vec<usize> = (0..7).map(|n| u(bell::<Nat>(n))).collect();
+        assert_eq!(bells, vec![1, 1, 2, 5, 15, 52, 203]);
+
+        assert_eq!(u(stirling1::<Nat>(4, 2)), 11);
+        assert_eq!(u(stirling2::<Nat>(5, 3)), 25);
+        assert_eq!(u(stirling1::<Nat>(0, 0)), 1);
+        assert_eq!(u(stirling2::<Nat>(3, 0)), 0);
+    }
+
+    #[test]
+    fn binomial_identities() {
+        type B = BinNat;
+        for n in 0..20usize {
+            // Row sums are powers of two.
+            let row: B = (0..=n)
+                .map(|k| binomial::<B>(n, k))
+                .fold(B::zero(), |a, c| a.add(&c));
+            assert_eq!(row, B::from_usize(2).pow(&B::from_usize(n)));
+            for k in 0..=n {
+                // Symmetry, and the falling-factorial formula.
+                assert_eq!(binomial::<B>(n, k), binomial::<B>(n, n - k));
+                let ff = falling_factorial(&B::from_usize(n), k);
+                assert_eq!(ff.div_exact(&factorial(k)), Some(binomial(n, k)));
+            }
+        }
+        // Vandermonde: C(m+n, r) = sum_k C(m, k) C(n, r-k).
+        let (m, n, r) = (7, 9, 6);
+        let rhs = (0..=r).fold(B::zero(), |a, k| {
+            a.add(&binomial::<B>(m, k).mul(&binomial(n, r - k)))
+        });
+        assert_eq!(binomial::<B>(m + n, r), rhs);
+        // Multinomial via factorials.
+        let direct = factorial::<B>(9)
+            .div_exact(&factorial::<B>(2).mul(&factorial(3)).mul(&factorial(4)))
+            .unwrap();
+        assert_eq!(multinomial::<B>(&[2, 3, 4]), direct);
+        // Rising factorial: x^(k) = (x+k-1)_k.
+        let x = B::from_usize(5);
+        assert_eq!(
+            rising_factorial(&x, 4),
+            falling_factorial(&B::from_usize(8), 4)
+        );
+    }
+
+    #[test]
+    fn sequence_identities() {
+        type B = BinNat;
+        for n in 1..30usize {
+            // L(n) = F(n-1) + F(n+1), F(2n) = F(n) L(n).
+            assert_eq!(lucas::<B>(n), fibonacci::<B>(n - 1).add(&fibonacci(n + 1)));
+            assert_eq!(fibonacci::<B>(2 * n), fibonacci::<B>(n).mul(&lucas(n)));
+        }
+        for n in 0..12usize {
+            // Catalan: C(n) = C(2n, n) / (n + 1).
+            let closed = binomial::<B>(2 * n, n).div_exact(&B::from_usize(n + 1));
+            assert_eq!(closed, Some(catalan(n)));
+            // Bell numbers sum the second-kind Stirling row; the first-kind row sums to n!.
+            let s2 = (0..=n).fold(B::zero(), |a, k| a.add(&stirling2(n, k)));
+            assert_eq!(s2, bell(n));
+            let s1 = (0..=n).fold(B::zero(), |a, k| a.add(&stirling1(n, k)));
+            assert_eq!(s1, factorial(n));
+        }
+    }
+
+    #[test]
+    fn bernoulli_and_harmonic() {
+        type B = BinNat;
+        let shown: Vec<String> = [0, 1, 2, 3, 4, 6, 12]
+            .iter()
+            .map(|&n| bernoulli::<B>(n).to_string())
+            .collect();
+        assert_eq!(
+            shown,
+            vec!["1", "-1/2", "1/6", "0", "-1/30", "1/42", "-691/2730"]
+        );
+
+        // Faulhaber: sum_{i<n} i^2 = (B_0 n^3 + 3 B_1 n^2 + 3 B_2 n) / 3.
+        let n = 10usize;
+        let sum_sq = (0..n).map(|i| i * i).sum::<usize>();
+        let nr = Rat::from_int(Int::from_nat(B::from_usize(n)));
+        let three = Rat::from_int(Int::from_nat(B::from_usize(3)));
+        let faulhaber = bernoulli::<B>(0)
+            .mul(&nr.mul(&nr).mul(&nr))
+            .add(&three.mul(&bernoulli(1)).mul(&nr.mul(&nr)))
+            .add(&three.mul(&bernoulli(2)).mul(&nr))
+            .div(&three)
+            .unwrap();
+        assert_eq!(
+            faulhaber,
+            Rat::from_int(Int::from_nat(B::from_usize(sum_sq)))
+        );
+
+        assert_eq!(harmonic::<B>(4).to_string(), "25/12");
+        assert_eq!(harmonic::<B>(0), Rat::zero());
+        // sum_{k=1..n} H(k) = (n + 1) H(n) - n.
+        let n = 15usize;
+        let lhs = (1..=n).fold(Rat::<B>::zero(), |a, k| a.add(&harmonic(k)));
+        let np1 = Rat::from_int(Int::from_nat(B::from_usize(n + 1)));
+        let rhs = np1
+            .mul(&harmonic(n))
+            .sub(&Rat::from_int(Int::from_nat(B::from_usize(n))));
+        assert_eq!(lhs, rhs);
+    }
+}
@@ -0,0 +1,10 @@
+#[macro_use]
+mod ops;
+
+pub mod c;
+pub mod comb;
+pub mod n;
+pub mod nt;
+pub mod q;
+pub mod r;
+pub mod z;
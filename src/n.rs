@@ -1,11 +1,196 @@
 use std::cmp::Ordering;
 use std::fmt;
+use std::sync::Arc;
+
+mod binary;
+mod notation;
+mod rec;
+
+pub use binary::BinNat;
+pub use notation::{NatDisplay, Notation, ParseNatError, ParseNatErrorKind};
+pub use rec::NatRange;
+
+pub(crate) use notation::parse_digits;
+
+// The operations every naturals backend provides. `Int`, `Rat`, `Real` and
+// `Complex` are generic over this, so the same tower can run on the unary
+// Peano `Nat` (to see the construction) or on `BinNat` (to get work done).
+pub trait Natural: Clone + fmt::Debug + fmt::Display + Eq + Ord + Send + Sync + 'static {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn is_zero(&self) -> bool;
+    fn succ(self) -> Self;
+    fn from_usize(n: usize) -> Self;
+    // Panics if the value does not fit in a usize.
+    fn to_usize(&self) -> usize;
+    fn add(&self, other: &Self) -> Self;
+    // Partial subtraction: returns None if other > self.
+    fn sub(&self, other: &Self) -> Option<Self>;
+    fn mul(&self, other: &Self) -> Self;
+    fn cmp_nat(&self, other: &Self) -> Ordering;
+    // Euclidean division: returns (quotient, remainder), or None if d == 0.
+    fn div_mod(&self, d: &Self) -> Option<(Self, Self)>;
+
+    fn sub_unchecked_gte(&self, other: &Self) -> Self {
+        // Only call when self >= other.
+        self.sub(other)
+            .expect("sub_unchecked_gte called with self < other")
+    }
+
+    fn gcd(&self, other: &Self) -> Self {
+        // Euclid: gcd(a, 0) = a, gcd(a, b) = gcd(b, a mod b)
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let (_, r) = a.div_mod(&b).expect("b != 0");
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    fn div_exact(&self, d: &Self) -> Option<Self> {
+        let (q, r) = self.div_mod(d)?;
+        if r.is_zero() { Some(q) } else { None }
+    }
+
+    // self^exp by square-and-multiply, reading exp in binary. 0^0 = 1.
+    fn pow(&self, exp: &Self) -> Self {
+        let two = Self::from_usize(2);
+        let mut base = self.clone();
+        let mut e = exp.clone();
+        let mut acc = Self::one();
+        while !e.is_zero() {
+            let (q, bit) = e.div_mod(&two).expect("2 != 0");
+            if !bit.is_zero() {
+                acc = acc.mul(&base);
+            }
+            e = q;
+            if !e.is_zero() {
+                base = base.mul(&base);
+            }
+        }
+        acc
+    }
+
+    // self^exp mod m, reducing after every step. None if m == 0.
+    fn mod_pow(&self, exp: &Self, m: &Self) -> Option<Self> {
+        let two = Self::from_usize(2);
+        let mut base = self.div_mod(m)?.1;
+        let mut e = exp.clone();
+        let mut acc = Self::one().div_mod(m)?.1;
+        while !e.is_zero() {
+            let (q, bit) = e.div_mod(&two).expect("2 != 0");
+            if !bit.is_zero() {
+                acc = acc.mul(&base).div_mod(m)?.1;
+            }
+            e = q;
+            if !e.is_zero() {
+                base = base.mul(&base).div_mod(m)?.1;
+            }
+        }
+        Some(acc)
+    }
+
+    // base^exp if it is <= limit, None as soon as a partial product exceeds it.
+    // Lets root searches probe huge exponents without building huge numbers.
+    fn pow_at_most(&self, exp: &Self, limit: &Self) -> Option<Self> {
+        if self.is_zero() || *self == Self::one() {
+            return Some(self.pow(exp)).filter(|p| p <= limit);
+        }
+        let mut acc = Self::one();
+        let mut e = exp.clone();
+        while !e.is_zero() {
+            acc = acc.mul(self);
+            if acc > *limit {
+                return None;
+            }
+            e = e.sub_unchecked_gte(&Self::one());
+        }
+        Some(acc)
+    }
+
+    // floor(self^(1/n)). None if n == 0.
+    fn nth_root(&self, n: &Self) -> Option<Self> {
+        if n.is_zero() {
+            return None;
+        }
+        let one = Self::one();
+        if *self <= one || *n == one {
+            return Some(self.clone());
+        }
+        // Double hi until hi^n > self, then bisect on lo^n <= self < hi^n.
+        let two = Self::from_usize(2);
+        let mut lo = one.clone();
+        let mut hi = two.clone();
+        while hi.pow_at_most(n, self).is_some() {
+            lo = hi.clone();
+            hi = hi.mul(&two);
+        }
+        while hi.sub_unchecked_gte(&lo) > one {
+            let mid = lo.add(&hi).div_mod(&two).expect("2 != 0").0;
+            if mid.pow_at_most(n, self).is_some() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Some(lo)
+    }
+
+    // floor(sqrt(self)).
+    fn isqrt(&self) -> Self {
+        self.nth_root(&Self::from_usize(2)).expect("2 != 0")
+    }
+
+    // True if self = m^k for some m and some k >= 2 (so 0 and 1 count).
+    fn is_perfect_power(&self) -> bool {
+        let one = Self::one();
+        if *self <= one {
+            return true;
+        }
+        // Only exponents with 2^k <= self can work.
+        let two = Self::from_usize(2);
+        let mut k = two.clone();
+        while two.pow_at_most(&k, self).is_some() {
+            let r = self.nth_root(&k).expect("k != 0");
+            if r.pow(&k) == *self {
+                return true;
+            }
+            k = k.succ();
+        }
+        false
+    }
+
+    // floor(log_base(self)). None if self == 0 or base < 2.
+    fn ilog(&self, base: &Self) -> Option<Self> {
+        if self.is_zero() || *base < Self::from_usize(2) {
+            return None;
+        }
+        let mut k = Self::zero();
+        let mut x = self.clone();
+        while x >= *base {
+            x = x.div_mod(base).expect("base != 0").0;
+            k = k.succ();
+        }
+        Some(k)
+    }
+}
 
 // Natural numbers (N) in Peano form: 0 | S(n).
-#[derive(Clone, Debug, PartialEq, Eq)]
+//
+// Each Succ points at its predecessor through an Arc, so chains are shared
+// rather than copied: cloning is O(1), `pred` and `sub` hand back a tail of
+// the existing chain, and `a.add(b)` builds its new nodes on top of `b`.
+// Values derived from one another therefore share their common tail.
+//
+// A value n is still a chain of n nodes, so anything that walks the chain
+// has to loop instead of recursing, or a Nat in the millions overflows the
+// stack. That includes PartialEq, Debug and Drop, written out by hand below.
+#[derive(Clone)]
 pub enum Nat {
     Zero,
-    Succ(Box<Nat>),
+    Succ(Arc<Nat>),
 }
 
 impl Nat {
@@ -14,7 +199,7 @@ impl Nat {
     }
 
     pub fn one() -> Nat {
-        Nat::Succ(Box::new(Nat::Zero))
+        Nat::Succ(Arc::new(Nat::Zero))
     }
 
     pub fn is_zero(&self) -> bool {
@@ -22,7 +207,7 @@ impl Nat {
     }
 
     pub fn succ(self) -> Nat {
-        Nat::Succ(Box::new(self))
+        Nat::Succ(Arc::new(self))
     }
 
     pub fn pred(&self) -> Option<&Nat> {
@@ -42,52 +227,64 @@ impl Nat {
     }
 
     pub fn to_usize(&self) -> usize {
-        match self {
-            Nat::Zero => 0,
-            Nat::Succ(n) => 1 + n.to_usize(),
+        let mut count = 0;
+        let mut cur = self;
+        while let Nat::Succ(n) = cur {
+            count += 1;
+            cur = n;
         }
+        count
     }
 
     pub fn add(&self, other: &Nat) -> Nat {
-        match self {
-            Nat::Zero => other.clone(),
-            Nat::Succ(n) => Nat::Succ(Box::new(n.add(other))),
-        }
+        // 0 + m = m, S(n) + m = S(n + m): apply succ to m, n times.
+        self.fold(other.clone(), Nat::succ)
     }
 
     // Partial subtraction on naturals: returns None if other > self.
     pub fn sub(&self, other: &Nat) -> Option<Nat> {
-        match (self, other) {
-            (Nat::Zero, Nat::Zero) => Some(Nat::Zero),
-            (Nat::Zero, Nat::Succ(_)) => None,
-            (Nat::Succ(n), Nat::Zero) => Some(Nat::Succ(Box::new((**n).clone()))),
-            (Nat::Succ(n), Nat::Succ(m)) => n.sub(m),
+        // S(n) - S(m) = n - m, n - 0 = n.
+        let mut a = self;
+        let mut b = other;
+        loop {
+            match (a, b) {
+                (_, Nat::Zero) => return Some(a.clone()),
+                (Nat::Zero, Nat::Succ(_)) => return None,
+                (Nat::Succ(n), Nat::Succ(m)) => {
+                    a = n;
+                    b = m;
+                }
+            }
         }
     }
 
     pub fn sub_unchecked_gte(&self, other: &Nat) -> Nat {
         // Only call when self >= other.
-        match (self, other) {
-            (Nat::Zero, Nat::Zero) => Nat::Zero,
-            (Nat::Succ(n), Nat::Zero) => Nat::Succ(Box::new((**n).clone())),
-            (Nat::Succ(n), Nat::Succ(m)) => n.sub_unchecked_gte(m),
-            _ => panic!("sub_unchecked_gte called with self < other"),
-        }
+        self.sub(other)
+            .expect("sub_unchecked_gte called with self < other")
     }
 
     pub fn mul(&self, other: &Nat) -> Nat {
-        match self {
-            Nat::Zero => Nat::Zero,
-            Nat::Succ(n) => n.mul(other).add(other),
-        }
+        // 0 * m = 0, S(n) * m = m + n * m: add m to zero, n times.
+        // Stacking m onto the running total keeps the cost at the size of the result.
+        self.fold(Nat::Zero, |acc| other.fold(acc, Nat::succ))
     }
 
     pub fn cmp_nat(&self, other: &Nat) -> Ordering {
-        match (self, other) {
-            (Nat::Zero, Nat::Zero) => Ordering::Equal,
-            (Nat::Zero, Nat::Succ(_)) => Ordering::Less,
-            (Nat::Succ(_), Nat::Zero) => Ordering::Greater,
-            (Nat::Succ(a), Nat::Succ(b)) => a.cmp_nat(b),
+        let mut a = self;
+        let mut b = other;
+        loop {
+            match (a, b) {
+                (Nat::Zero, Nat::Zero) => return Ordering::Equal,
+                (Nat::Zero, Nat::Succ(_)) => return Ordering::Less,
+                (Nat::Succ(_), Nat::Zero) => return Ordering::Greater,
+                // Same node, same tail: no need to walk the rest.
+                (Nat::Succ(n), Nat::Succ(m)) if Arc::ptr_eq(n, m) => return Ordering::Equal,
+                (Nat::Succ(n), Nat::Succ(m)) => {
+                    a = n;
+                    b = m;
+                }
+            }
         }
     }
 
@@ -123,9 +320,99 @@ impl Nat {
     }
 }
 
+impl Natural for Nat {
+    fn zero() -> Nat {
+        Nat::zero()
+    }
+
+    fn one() -> Nat {
+        Nat::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        Nat::is_zero(self)
+    }
+
+    fn succ(self) -> Nat {
+        Nat::succ(self)
+    }
+
+    fn from_usize(n: usize) -> Nat {
+        Nat::from_usize(n)
+    }
+
+    fn to_usize(&self) -> usize {
+        Nat::to_usize(self)
+    }
+
+    fn add(&self, other: &Nat) -> Nat {
+        Nat::add(self, other)
+    }
+
+    fn sub(&self, other: &Nat) -> Option<Nat> {
+        Nat::sub(self, other)
+    }
+
+    fn sub_unchecked_gte(&self, other: &Nat) -> Nat {
+        Nat::sub_unchecked_gte(self, other)
+    }
+
+    fn mul(&self, other: &Nat) -> Nat {
+        Nat::mul(self, other)
+    }
+
+    fn cmp_nat(&self, other: &Nat) -> Ordering {
+        Nat::cmp_nat(self, other)
+    }
+
+    fn div_mod(&self, d: &Nat) -> Option<(Nat, Nat)> {
+        Nat::div_mod(self, d)
+    }
+
+    fn gcd(&self, other: &Nat) -> Nat {
+        Nat::gcd(self, other)
+    }
+
+    fn div_exact(&self, d: &Nat) -> Option<Nat> {
+        Nat::div_exact(self, d)
+    }
+}
+
+impl Drop for Nat {
+    fn drop(&mut self) {
+        // Detach the tail before this node goes away, then unlink it one node
+        // at a time. Each detached node is Succ(Zero), so dropping it is shallow.
+        // Stop at the first node someone else still holds: that tail lives on.
+        let mut rest = match self {
+            Nat::Succ(n) => match Arc::get_mut(n) {
+                Some(inner) => std::mem::replace(inner, Nat::Zero),
+                None => return,
+            },
+            Nat::Zero => return,
+        };
+        while let Nat::Succ(n) = &mut rest {
+            match Arc::get_mut(n) {
+                Some(inner) => {
+                    let next = std::mem::replace(inner, Nat::Zero);
+                    rest = next;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+impl PartialEq for Nat {
+    fn eq(&self, other: &Nat) -> bool {
+        self.cmp_nat(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Nat {}
+
 impl PartialOrd for Nat {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp_nat(other))
+        Some(self.cmp(other))
     }
 }
 
@@ -135,12 +422,42 @@ impl Ord for Nat {
     }
 }
 
+impl fmt::Debug for Nat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Same text the derive would print (Succ(Succ(Zero))), without recursing.
+        let n = self.to_usize();
+        for _ in 0..n {
+            write!(f, "Succ(")?;
+        }
+        write!(f, "Zero")?;
+        for _ in 0..n {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for Nat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.to_usize())
     }
 }
 
+// Operators. `-` panics if the right side is larger and `/`, `%` panic on a
+// zero divisor; `sub` and `div_mod` are the checked forms.
+forward_binop!([] Nat, Add::add, AddAssign::add_assign, |a, b| Nat::add(a, b));
+forward_binop!([] Nat, Sub::sub, SubAssign::sub_assign, |a, b| {
+    Nat::sub(a, b).expect("Nat subtraction underflow: rhs > lhs")
+});
+forward_binop!([] Nat, Mul::mul, MulAssign::mul_assign, |a, b| Nat::mul(a, b));
+forward_binop!([] Nat, Div::div, DivAssign::div_assign, |a, b| {
+    Nat::div_mod(a, b).expect("Nat division by zero").0
+});
+forward_binop!([] Nat, Rem::rem, RemAssign::rem_assign, |a, b| {
+    Nat::div_mod(a, b).expect("Nat division by zero").1
+});
+forward_sum_product!([] Nat, zero = Nat::zero(), one = Nat::one());
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,4 +474,168 @@ mod tests {
         assert_eq!(q.to_usize(), 2);
         assert_eq!(r.to_usize(), 1);
     }
+
+    fn sum_to<N: Natural>(n: usize) -> N {
+        let mut acc = N::zero();
+        for k in 1..=n {
+            acc = acc.add(&N::from_usize(k));
+        }
+        acc
+    }
+
+    #[test]
+    fn backends_agree() {
+        let peano: Nat = sum_to(20);
+        let binary: BinNat = sum_to(20);
+        assert_eq!(peano.to_usize(), 210);
+        assert_eq!(Natural::to_usize(&binary), 210);
+
+        let (q, r) = Natural::div_mod(&binary, &BinNat::from_usize(11)).unwrap();
+        assert_eq!((q.to_usize(), r.to_usize()), (19, 1));
+        assert_eq!(
+            Natural::gcd(&binary, &BinNat::from_usize(84)).to_usize(),
+            42
+        );
+    }
+
+    #[test]
+    fn deep_nats_do_not_overflow_the_stack() {
+        let n = 1_000_000;
+        let a = Nat::from_usize(n);
+        let b = a.clone();
+        assert_eq!(a, b);
+        assert_eq!(a.to_usize(), n);
+        assert_eq!(format!("{}", a), "1000000");
+
+        let c = a.add(&Nat::one());
+        assert_eq!(a.cmp_nat(&c), Ordering::Less);
+        assert_eq!(c.sub(&a), Some(Nat::one()));
+        assert_eq!(c.sub_unchecked_gte(&Nat::one()), a);
+        assert!(a.sub(&c).is_none());
+
+        let thousand = Nat::from_usize(1000);
+        assert_eq!(thousand.mul(&thousand), a);
+        drop(a);
+        drop(b);
+        drop(c);
+    }
+
+    // The Arc behind the outermost Succ, i.e. the predecessor's node.
+    fn link(n: &Nat) -> &Arc<Nat> {
+        match n {
+            Nat::Succ(p) => p,
+            Nat::Zero => panic!("zero has no predecessor"),
+        }
+    }
+
+    #[test]
+    fn nat_shares_tails() {
+        let ten = Nat::from_usize(10);
+        let three = Nat::from_usize(3);
+
+        // 10 - 3 is the node three steps down 10's own chain.
+        let seven = ten.sub(&three).unwrap();
+        let mut cur = &ten;
+        for _ in 0..3 {
+            cur = cur.pred().unwrap();
+        }
+        assert!(Arc::ptr_eq(link(&seven), link(cur)));
+
+        // 3 + 10 stacks three new nodes on top of 10.
+        let thirteen = three.add(&ten);
+        let base = thirteen.pred().unwrap().pred().unwrap().pred().unwrap();
+        assert!(Arc::ptr_eq(link(base), link(&ten)));
+
+        // Remainders are tails of the dividend.
+        let (_, r) = ten.div_mod(&three).unwrap();
+        assert_eq!(r, Nat::one());
+        assert!(Arc::ptr_eq(
+            link(&r),
+            link(ten.sub(&Nat::from_usize(9)).as_ref().unwrap())
+        ));
+    }
+
+    #[test]
+    fn shared_deep_nats_drop_safely() {
+        let a = Nat::from_usize(1_000_000);
+        let b = a.sub(&Nat::from_usize(10)).unwrap();
+        let c = a.clone();
+        drop(a);
+        assert_eq!(b.to_usize(), 999_990);
+        drop(c);
+        assert_eq!(b.add(&Nat::from_usize(10)).to_usize(), 1_000_000);
+    }
+
+    #[test]
+    fn nat_debug_shows_structure() {
+        assert_eq!(format!("{:?}", Nat::from_usize(2)), "Succ(Succ(Zero))");
+        assert_eq!(format!("{:?}", Nat::zero()), "Zero");
+    }
+
+    #[test]
+    fn nat_operators() {
+        let a = Nat::from_usize(7);
+        let b = Nat::from_usize(3);
+        assert_eq!(&a + &b, Nat::from_usize(10));
+        assert_eq!(&a - &b, Nat::from_usize(4));
+        assert_eq!(&a * &b, Nat::from_usize(21));
+        assert_eq!(&a / &b, Nat::from_usize(2));
+        assert_eq!(&a % &b, Nat::one());
+
+        let mut c = a.clone();
+        c += &b;
+        c *= Nat::from_usize(2);
+        c -= b;
+        assert_eq!(c, Nat::from_usize(17));
+
+        let xs: Vec<Nat> = (1..=4).map(Nat::from_usize).collect();
+        assert_eq!(xs.iter().sum::<Nat>(), Nat::from_usize(10));
+        assert_eq!(xs.into_iter().product::<Nat>(), Nat::from_usize(24));
+    }
+
+    #[test]
+    #[should_panic(expected = "underflow")]
+    fn nat_sub_operator_panics_below_zero() {
+        let _ = Nat::from_usize(1) - Nat::from_usize(2);
+    }
+
+    fn powers_and_roots<N: Natural>() {
+        let n = |v: usize| N::from_usize(v);
+        assert_eq!(n(3).pow(&n(4)), n(81));
+        assert_eq!(n(0).pow(&n(0)), n(1));
+        assert_eq!(n(4).mod_pow(&n(13), &n(497)), Some(n(445)));
+        assert_eq!(n(4).mod_pow(&n(13), &n(1)), Some(n(0)));
+        assert_eq!(n(4).mod_pow(&n(13), &n(0)), None);
+
+        assert_eq!(n(99).isqrt(), n(9));
+        assert_eq!(n(100).isqrt(), n(10));
+        assert_eq!(n(0).isqrt(), n(0));
+        assert_eq!(n(80).nth_root(&n(3)), Some(n(4)));
+        assert_eq!(n(81).nth_root(&n(4)), Some(n(3)));
+        assert_eq!(n(81).nth_root(&n(100)), Some(n(1)));
+        assert_eq!(n(81).nth_root(&n(0)), None);
+
+        let perfect: Vec<usize> = (0..70).filter(|&v| n(v).is_perfect_power()).collect();
+        assert_eq!(perfect, vec![0, 1, 4, 8, 9, 16, 25, 27, 32, 36, 49, 64]);
+
+        assert_eq!(n(1000).ilog(&n(10)), Some(n(3)));
+        assert_eq!(n(999).ilog(&n(10)), Some(n(2)));
+        assert_eq!(n(1).ilog(&n(2)), Some(n(0)));
+        assert_eq!(n(0).ilog(&n(2)), None);
+        assert_eq!(n(8).ilog(&n(1)), None);
+    }
+
+    #[test]
+    fn powers_and_roots_on_both_backends() {
+        powers_and_roots::<Nat>();
+        powers_and_roots::<BinNat>();
+
+        // Big enough that only the binary backend is practical.
+        let two = BinNat::from_usize(2);
+        let big = two.pow(&BinNat::from_usize(200));
+        assert_eq!(big.isqrt(), two.pow(&BinNat::from_usize(100)));
+        assert_eq!(big.ilog(&two), Some(BinNat::from_usize(200)));
+        assert!(big.is_perfect_power());
+        assert!(!big.add(&BinNat::one()).is_perfect_power());
+    }
 }
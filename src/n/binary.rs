@@ -0,0 +1,345 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::Natural;
+
+// Natural numbers (N) in binary: a bit vector packed into 32-bit limbs,
+// least significant limb first. Same API as the Peano `Nat`, but the cost of
+// every operation grows with the number of bits instead of the value itself.
+//
+// Invariant: no trailing zero limbs, so zero is the empty vector and every
+// value has exactly one representation (derived `Eq` is value equality).
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BinNat {
+    limbs: Vec<u32>,
+}
+
+const LIMB_BITS: usize = 32;
+
+impl BinNat {
+    pub fn zero() -> BinNat {
+        BinNat { limbs: Vec::new() }
+    }
+
+    pub fn one() -> BinNat {
+        BinNat { limbs: vec![1] }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn from_usize(n: usize) -> BinNat {
+        let mut n = n as u64;
+        let mut limbs = Vec::new();
+        while n > 0 {
+            limbs.push(n as u32);
+            n >>= LIMB_BITS;
+        }
+        BinNat { limbs }
+    }
+
+    pub fn to_usize(&self) -> usize {
+        let mut out: u64 = 0;
+        for (i, &limb) in self.limbs.iter().enumerate() {
+            assert!(i < 2, "BinNat does not fit in usize");
+            out |= (limb as u64) << (i * LIMB_BITS);
+        }
+        usize::try_from(out).expect("BinNat does not fit in usize")
+    }
+
+    // Number of significant bits (0 for zero).
+    pub fn bit_len(&self) -> usize {
+        match self.limbs.last() {
+            None => 0,
+            Some(&top) => self.limbs.len() * LIMB_BITS - top.leading_zeros() as usize,
+        }
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        match self.limbs.get(i / LIMB_BITS) {
+            None => false,
+            Some(&limb) => (limb >> (i % LIMB_BITS)) & 1 == 1,
+        }
+    }
+
+    fn set_bit(&mut self, i: usize) {
+        let idx = i / LIMB_BITS;
+        if self.limbs.len() <= idx {
+            self.limbs.resize(idx + 1, 0);
+        }
+        self.limbs[idx] |= 1 << (i % LIMB_BITS);
+    }
+
+    fn normalize(mut self) -> BinNat {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    // self * 2 + bit, in place.
+    fn shl1_push(&mut self, bit: bool) {
+        let mut carry = bit as u32;
+        for limb in self.limbs.iter_mut() {
+            let next = *limb >> (LIMB_BITS - 1);
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+        if carry != 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    pub fn add(&self, other: &BinNat) -> BinNat {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (&self.limbs, &other.limbs)
+        } else {
+            (&other.limbs, &self.limbs)
+        };
+        let mut limbs = Vec::with_capacity(long.len() + 1);
+        let mut carry = 0u64;
+        for (i, &a) in long.iter().enumerate() {
+            let b = short.get(i).copied().unwrap_or(0);
+            let s = a as u64 + b as u64 + carry;
+            limbs.push(s as u32);
+            carry = s >> LIMB_BITS;
+        }
+        if carry != 0 {
+            limbs.push(carry as u32);
+        }
+        BinNat { limbs }
+    }
+
+    // Partial subtraction on naturals: returns None if other > self.
+    pub fn sub(&self, other: &BinNat) -> Option<BinNat> {
+        if self.cmp_nat(other) == Ordering::Less {
+            return None;
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let b = other.limbs.get(i).copied().unwrap_or(0);
+            let mut d = a as i64 - b as i64 - borrow;
+            borrow = 0;
+            if d < 0 {
+                d += 1 << LIMB_BITS;
+                borrow = 1;
+            }
+            limbs.push(d as u32);
+        }
+        Some(BinNat { limbs }.normalize())
+    }
+
+    pub fn mul(&self, other: &BinNat) -> BinNat {
+        if self.is_zero() || other.is_zero() {
+            return BinNat::zero();
+        }
+        // Schoolbook multiplication, one limb row at a time.
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let t = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = t as u32;
+                carry = t >> LIMB_BITS;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BinNat { limbs }.normalize()
+    }
+
+    pub fn cmp_nat(&self, other: &BinNat) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+
+    // Division by a single limb; returns (quotient, remainder).
+    fn div_mod_small(&self, d: u32) -> (BinNat, u32) {
+        let mut limbs = vec![0u32; self.limbs.len()];
+        let mut rem = 0u64;
+        for i in (0..self.limbs.len()).rev() {
+            let cur = (rem << LIMB_BITS) | self.limbs[i] as u64;
+            limbs[i] = (cur / d as u64) as u32;
+            rem = cur % d as u64;
+        }
+        (BinNat { limbs }.normalize(), rem as u32)
+    }
+
+    // Binary long division: bring down one bit at a time, subtract when possible.
+    pub fn div_mod(&self, d: &BinNat) -> Option<(BinNat, BinNat)> {
+        if d.is_zero() {
+            return None;
+        }
+        if d.limbs.len() == 1 {
+            let (q, r) = self.div_mod_small(d.limbs[0]);
+            return Some((q, BinNat::from_usize(r as usize)));
+        }
+        let mut q = BinNat::zero();
+        let mut r = BinNat::zero();
+        for i in (0..self.bit_len()).rev() {
+            r.shl1_push(self.bit(i));
+            if r.cmp_nat(d) != Ordering::Less {
+                r = r.sub(d).expect("r >= d");
+                q.set_bit(i);
+            }
+        }
+        Some((q, r))
+    }
+}
+
+impl Natural for BinNat {
+    fn zero() -> BinNat {
+        BinNat::zero()
+    }
+
+    fn one() -> BinNat {
+        BinNat::one()
+    }
+
+    fn is_zero(&self) -> bool {
+        BinNat::is_zero(self)
+    }
+
+    fn succ(self) -> BinNat {
+        BinNat::add(&self, &BinNat::one())
+    }
+
+    fn from_usize(n: usize) -> BinNat {
+        BinNat::from_usize(n)
+    }
+
+    fn to_usize(&self) -> usize {
+        BinNat::to_usize(self)
+    }
+
+    fn add(&self, other: &BinNat) -> BinNat {
+        BinNat::add(self, other)
+    }
+
+    fn sub(&self, other: &BinNat) -> Option<BinNat> {
+        BinNat::sub(self, other)
+    }
+
+    fn mul(&self, other: &BinNat) -> BinNat {
+        BinNat::mul(self, other)
+    }
+
+    fn cmp_nat(&self, other: &BinNat) -> Ordering {
+        BinNat::cmp_nat(self, other)
+    }
+
+    fn div_mod(&self, d: &BinNat) -> Option<(BinNat, BinNat)> {
+        BinNat::div_mod(self, d)
+    }
+}
+
+impl PartialOrd for BinNat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BinNat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_nat(other)
+    }
+}
+
+impl fmt::Display for BinNat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Peel off base-10^9 chunks, least significant first.
+        const CHUNK: u32 = 1_000_000_000;
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut chunks = Vec::new();
+        let mut n = self.clone();
+        while !n.is_zero() {
+            let (q, r) = n.div_mod_small(CHUNK);
+            chunks.push(r);
+            n = q;
+        }
+        let mut out = chunks.pop().expect("nonzero").to_string();
+        for c in chunks.iter().rev() {
+            out.push_str(&format!("{:09}", c));
+        }
+        write!(f, "{}", out)
+    }
+}
+
+impl fmt::Debug for BinNat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BinNat({})", self)
+    }
+}
+
+// Operators, with the same panic policy as `Nat`.
+forward_binop!([] BinNat, Add::add, AddAssign::add_assign, |a, b| BinNat::add(a, b));
+forward_binop!([] BinNat, Sub::sub, SubAssign::sub_assign, |a, b| {
+    BinNat::sub(a, b).expect("BinNat subtraction underflow: rhs > lhs")
+});
+forward_binop!([] BinNat, Mul::mul, MulAssign::mul_assign, |a, b| BinNat::mul(a, b));
+forward_binop!([] BinNat, Div::div, DivAssign::div_assign, |a, b| {
+    BinNat::div_mod(a, b).expect("BinNat division by zero").0
+});
+forward_binop!([] BinNat, Rem::rem, RemAssign::rem_assign, |a, b| {
+    BinNat::div_mod(a, b).expect("BinNat division by zero").1
+});
+forward_sum_product!([] BinNat, zero = BinNat::zero(), one = BinNat::one());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(n: u128) -> BinNat {
+        let mut out = BinNat::zero();
+        for i in (0..128).rev() {
+            out.shl1_push((n >> i) & 1 == 1);
+        }
+        out.normalize()
+    }
+
+    #[test]
+    fn binnat_matches_u128_arithmetic() {
+        let samples: [u128; 6] = [
+            0,
+            1,
+            7,
+            4_294_967_296,
+            123_456_789_012_345,
+            u64::MAX as u128,
+        ];
+        for &a in &samples {
+            for &b in &samples {
+                let (x, y) = (big(a), big(b));
+                assert_eq!(x.add(&y), big(a + b));
+                assert_eq!(x.mul(&y), big(a * b));
+                assert_eq!(x.cmp_nat(&y), a.cmp(&b));
+                assert_eq!(x.sub(&y), a.checked_sub(b).map(big));
+                match x.div_mod(&y) {
+                    None => assert_eq!(b, 0),
+                    Some((q, r)) => {
+                        assert_eq!(q, big(a / b));
+                        assert_eq!(r, big(a % b));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn binnat_displays_large_values() {
+        let two = BinNat::from_usize(2);
+        let mut p = BinNat::one();
+        for _ in 0..100 {
+            p = p.mul(&two);
+        }
+        assert_eq!(p.bit_len(), 101);
+        assert_eq!(format!("{}", p), "1267650600228229401496703205376");
+        assert_eq!(BinNat::from_usize(1_000_000_000).to_usize(), 1_000_000_000);
+        assert_eq!(format!("{}", BinNat::zero()), "0");
+    }
+}
@@ -0,0 +1,277 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{BinNat, Nat, Natural};
+
+// The textual notations a natural can be written in:
+// - Decimal: 3
+// - Binary:  0b11
+// - Hex:     0x3
+// - Tally:   |||   (zero has no marks, so it is written 0)
+// - Peano:   S(S(S(0)))
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+    Decimal,
+    Binary,
+    Hex,
+    Tally,
+    Peano,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseNatErrorKind {
+    Empty,
+    InvalidDigit(char),
+    // Peano syntax: the given text was expected at this position.
+    Expected(&'static str),
+    TrailingInput,
+}
+
+// A parse failure and the byte offset (into the trimmed input) where it happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseNatError {
+    kind: ParseNatErrorKind,
+    pos: usize,
+}
+
+impl ParseNatError {
+    pub(crate) fn new(kind: ParseNatErrorKind, pos: usize) -> ParseNatError {
+        ParseNatError { kind, pos }
+    }
+
+    pub fn kind(&self) -> &ParseNatErrorKind {
+        &self.kind
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl fmt::Display for ParseNatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseNatErrorKind::Empty => write!(f, "cannot parse a natural from empty input"),
+            ParseNatErrorKind::InvalidDigit(c) => {
+                write!(f, "invalid digit {:?} at position {}", c, self.pos)
+            }
+            ParseNatErrorKind::Expected(what) => {
+                write!(f, "expected {:?} at position {}", what, self.pos)
+            }
+            ParseNatErrorKind::TrailingInput => {
+                write!(f, "unexpected trailing input at position {}", self.pos)
+            }
+        }
+    }
+}
+
+impl Error for ParseNatError {}
+
+// Parse any notation above into any backend. The notation is picked from the
+// first characters: `0b`, `0x`, `|`, `S`, otherwise decimal.
+pub(crate) fn parse_nat<N: Natural>(s: &str) -> Result<N, ParseNatError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseNatError::new(ParseNatErrorKind::Empty, 0));
+    }
+    if let Some(digits) = s.strip_prefix("0b").or_else(|| s.strip_prefix("0B")) {
+        parse_digits(digits, 2, 2)
+    } else if let Some(digits) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        parse_digits(digits, 16, 2)
+    } else if s.starts_with('|') {
+        parse_tally(s)
+    } else if s.starts_with('S') {
+        parse_peano(s)
+    } else {
+        parse_digits(s, 10, 0)
+    }
+}
+
+// Positional digits in the given radix; `offset` is where `digits` starts in
+// the input, so error positions point into the original string.
+pub(crate) fn parse_digits<N: Natural>(
+    digits: &str,
+    radix: u32,
+    offset: usize,
+) -> Result<N, ParseNatError> {
+    if digits.is_empty() {
+        return Err(ParseNatError::new(
+            ParseNatErrorKind::Expected("digits"),
+            offset,
+        ));
+    }
+    let base = N::from_usize(radix as usize);
+    let mut acc = N::zero();
+    for (i, c) in digits.char_indices() {
+        let d = c
+            .to_digit(radix)
+            .ok_or_else(|| ParseNatError::new(ParseNatErrorKind::InvalidDigit(c), offset + i))?;
+        acc = acc.mul(&base).add(&N::from_usize(d as usize));
+    }
+    Ok(acc)
+}
+
+fn parse_tally<N: Natural>(s: &str) -> Result<N, ParseNatError> {
+    let mut acc = N::zero();
+    for (i, c) in s.char_indices() {
+        if c != '|' {
+            return Err(ParseNatError::new(ParseNatErrorKind::InvalidDigit(c), i));
+        }
+        acc = acc.succ();
+    }
+    Ok(acc)
+}
+
+fn parse_peano<N: Natural>(s: &str) -> Result<N, ParseNatError> {
+    // S( ... S( 0 ) ... ): count the openers, then expect as many closers.
+    let bytes = s.as_bytes();
+    let mut pos = 0;
+    let mut depth = 0usize;
+    while bytes[pos..].starts_with(b"S(") {
+        pos += 2;
+        depth += 1;
+    }
+    if bytes.get(pos) != Some(&b'0') {
+        return Err(ParseNatError::new(
+            ParseNatErrorKind::Expected(if depth == 0 { "S(" } else { "0" }),
+            pos,
+        ));
+    }
+    pos += 1;
+    for _ in 0..depth {
+        if bytes.get(pos) != Some(&b')') {
+            return Err(ParseNatError::new(ParseNatErrorKind::Expected(")"), pos));
+        }
+        pos += 1;
+    }
+    if pos != bytes.len() {
+        return Err(ParseNatError::new(ParseNatErrorKind::TrailingInput, pos));
+    }
+    let mut acc = N::zero();
+    for _ in 0..depth {
+        acc = acc.succ();
+    }
+    Ok(acc)
+}
+
+impl FromStr for Nat {
+    type Err = ParseNatError;
+
+    fn from_str(s: &str) -> Result<Nat, ParseNatError> {
+        parse_nat(s)
+    }
+}
+
+impl FromStr for BinNat {
+    type Err = ParseNatError;
+
+    fn from_str(s: &str) -> Result<BinNat, ParseNatError> {
+        parse_nat(s)
+    }
+}
+
+// A Nat paired with the notation to print it in; see `Nat::display_as`.
+pub struct NatDisplay<'a> {
+    nat: &'a Nat,
+    notation: Notation,
+}
+
+impl Nat {
+    pub fn display_as(&self, notation: Notation) -> NatDisplay<'_> {
+        NatDisplay {
+            nat: self,
+            notation,
+        }
+    }
+}
+
+impl fmt::Display for NatDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.nat.to_usize();
+        match self.notation {
+            Notation::Decimal => write!(f, "{}", n),
+            Notation::Binary => write!(f, "{:#b}", n),
+            Notation::Hex => write!(f, "{:#x}", n),
+            Notation::Tally if n == 0 => write!(f, "0"),
+            Notation::Tally => {
+                for _ in 0..n {
+                    write!(f, "|")?;
+                }
+                Ok(())
+            }
+            Notation::Peano => {
+                for _ in 0..n {
+                    write!(f, "S(")?;
+                }
+                write!(f, "0")?;
+                for _ in 0..n {
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_notation() {
+        let three = Nat::from_usize(3);
+        for s in ["3", " 3 ", "0b11", "0x3", "|||", "S(S(S(0)))"] {
+            assert_eq!(s.parse::<Nat>(), Ok(three.clone()), "{}", s);
+        }
+        assert_eq!("0".parse::<Nat>(), Ok(Nat::zero()));
+        assert_eq!("0xff".parse::<BinNat>(), Ok(BinNat::from_usize(255)));
+        assert_eq!(
+            "340282366920938463463374607431768211456"
+                .parse::<BinNat>()
+                .map(|n| n.bit_len()),
+            Ok(129)
+        );
+    }
+
+    #[test]
+    fn notations_round_trip() {
+        for n in [0, 1, 5, 42] {
+            let x = Nat::from_usize(n);
+            for notation in [
+                Notation::Decimal,
+                Notation::Binary,
+                Notation::Hex,
+                Notation::Tally,
+                Notation::Peano,
+            ] {
+                let text = x.display_as(notation).to_string();
+                assert_eq!(text.parse::<Nat>(), Ok(x.clone()), "{}", text);
+            }
+        }
+        assert_eq!(
+            Nat::from_usize(3).display_as(Notation::Peano).to_string(),
+            "S(S(S(0)))"
+        );
+        assert_eq!(
+            Nat::from_usize(4).display_as(Notation::Tally).to_string(),
+            "||||"
+        );
+    }
+
+    #[test]
+    fn reports_malformed_input() {
+        let err = |s: &str| s.parse::<Nat>().unwrap_err();
+        assert_eq!(err("").kind(), &ParseNatErrorKind::Empty);
+        assert_eq!(err("12a").kind(), &ParseNatErrorKind::InvalidDigit('a'));
+        assert_eq!(err("12a").position(), 2);
+        assert_eq!(err("0b102").position(), 4);
+        assert_eq!(err("0x").kind(), &ParseNatErrorKind::Expected("digits"));
+        assert_eq!(err("||x").position(), 2);
+        assert_eq!(err("S(S(0)").kind(), &ParseNatErrorKind::Expected(")"));
+        assert_eq!(err("S(S(0)").position(), 6);
+        assert_eq!(err("S(1)").kind(), &ParseNatErrorKind::Expected("0"));
+        assert_eq!(err("S(0))").kind(), &ParseNatErrorKind::TrailingInput);
+        assert_eq!(err("1x").to_string(), "invalid digit 'x' at position 1");
+    }
+}
@@ -0,0 +1,162 @@
+use super::Nat;
+
+// Recursion schemes on the Peano structure. Every Nat is "apply succ n times
+// to zero", so the natural way to consume one is to apply some other function
+// n times (`fold`), optionally looking at which step we are on (`rec`). Going
+// the other way, `unfold` counts how many steps a process takes.
+//
+// All of these loop over the chain instead of recursing, so they are as
+// stack-safe as the rest of `Nat`.
+impl Nat {
+    // Iteration: fold(n, init, f) = f(f(...f(init)...)), with f applied n times.
+    //   fold(0, x, f)    = x
+    //   fold(S(k), x, f) = f(fold(k, x, f))
+    pub fn fold<T>(&self, init: T, mut f: impl FnMut(T) -> T) -> T {
+        let mut acc = init;
+        let mut cur = self;
+        while let Nat::Succ(n) = cur {
+            acc = f(acc);
+            cur = n;
+        }
+        acc
+    }
+
+    // Primitive recursion: like `fold`, but each step also sees the
+    // predecessor it is stepping from.
+    //   rec(0, base, step)    = base
+    //   rec(S(k), base, step) = step(k, rec(k, base, step))
+    pub fn rec<T>(&self, base: T, mut step: impl FnMut(&Nat, T) -> T) -> T {
+        let mut k = Nat::zero();
+        self.fold(base, |acc| {
+            let next = step(&k, acc);
+            k = k.clone().succ();
+            next
+        })
+    }
+
+    // The naturals below self, in order: 0, 1, ..., self - 1.
+    pub fn range(&self) -> NatRange {
+        NatRange {
+            next: Nat::zero(),
+            remaining: self.clone(),
+        }
+    }
+
+    // The infinite stream seed, f(seed), f(f(seed)), ...
+    pub fn iterate<T: Clone>(seed: T, mut f: impl FnMut(&T) -> T) -> impl Iterator<Item = T> {
+        std::iter::successors(Some(seed), move |x| Some(f(x)))
+    }
+
+    // All naturals, built by iterating succ from zero.
+    pub fn naturals() -> impl Iterator<Item = Nat> {
+        Nat::iterate(Nat::zero(), |n| n.clone().succ())
+    }
+
+    // The dual of fold: run `step` until it returns None and count the steps.
+    //   unfold(s, step) = 0                    if step(s) = None
+    //   unfold(s, step) = S(unfold(s', step))  if step(s) = Some(s')
+    // Loops forever if `step` never stops.
+    pub fn unfold<S>(seed: S, mut step: impl FnMut(S) -> Option<S>) -> Nat {
+        let mut out = Nat::zero();
+        let mut state = seed;
+        while let Some(next) = step(state) {
+            out = out.succ();
+            state = next;
+        }
+        out
+    }
+
+    // Worked examples: the usual recursive definitions, written as combinators.
+    // (`add` and `mul` in n.rs are defined the same way.) They are named apart
+    // from `Natural::pow` and `comb::factorial`, which compute the same values
+    // faster or for any backend, so `x.pow(&e)` always means the trait method.
+
+    //   a^0 = 1,  a^S(k) = a^k * a
+    pub fn pow_by_fold(&self, exp: &Nat) -> Nat {
+        exp.fold(Nat::one(), |acc| acc.mul(self))
+    }
+
+    //   0! = 1,  S(k)! = S(k) * k!
+    pub fn factorial_by_rec(&self) -> Nat {
+        self.rec(Nat::one(), |k, acc| k.clone().succ().mul(&acc))
+    }
+}
+
+// Iterator returned by `Nat::range`. Counts up with `next` while walking
+// `remaining` down, so each step is O(1).
+pub struct NatRange {
+    next: Nat,
+    remaining: Nat,
+}
+
+impl Iterator for NatRange {
+    type Item = Nat;
+
+    fn next(&mut self) -> Option<Nat> {
+        let rest = self.remaining.pred()?.clone();
+        self.remaining = rest;
+        let out = self.next.clone();
+        self.next = out.clone().succ();
+        Some(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::Natural;
+
+    #[test]
+    fn fold_and_rec_follow_the_recursion_equations() {
+        let five = Nat::from_usize(5);
+        assert_eq!(five.fold(0usize, |x| x + 2), 10);
+        assert_eq!(Nat::zero().fold("base", |_| "step"), "base");
+
+        // rec sees predecessors 0, 1, 2, 3, 4 in order.
+        let seen = five.rec(Vec::new(), |k, mut acc| {
+            acc.push(k.to_usize());
+            acc
+        });
+        assert_eq!(seen, vec![0, 1, 2, 3, 4]);
+
+        // Triangular numbers: T(S(k)) = T(k) + S(k).
+        let t = five.rec(Nat::zero(), |k, acc| acc.add(&k.clone().succ()));
+        assert_eq!(t.to_usize(), 15);
+    }
+
+    #[test]
+    fn worked_examples() {
+        let two = Nat::from_usize(2);
+        assert_eq!(two.pow_by_fold(&Nat::from_usize(10)).to_usize(), 1024);
+        assert_eq!(Nat::zero().pow_by_fold(&Nat::zero()), Nat::one());
+        assert_eq!(Nat::from_usize(5).factorial_by_rec().to_usize(), 120);
+        assert_eq!(Nat::zero().factorial_by_rec(), Nat::one());
+
+        // The fold agrees with square-and-multiply `Natural::pow`.
+        for a in 0..4 {
+            for e in 0..6 {
+                let (a, e) = (Nat::from_usize(a), Nat::from_usize(e));
+                assert_eq!(a.pow_by_fold(&e), Natural::pow(&a, &e));
+            }
+        }
+    }
+
+    #[test]
+    fn range_iterate_and_unfold() {
+        let below: Vec<usize> = Nat::from_usize(4).range().map(|n| n.to_usize()).collect();
+        assert_eq!(below, vec![0, 1, 2, 3]);
+        assert_eq!(Nat::zero().range().count(), 0);
+
+        let first: Vec<usize> = Nat::naturals().take(3).map(|n| n.to_usize()).collect();
+        assert_eq!(first, vec![0, 1, 2]);
+        let doubling: Vec<usize> = Nat::iterate(1usize, |x| x * 2).take(4).collect();
+        assert_eq!(doubling, vec![1, 2, 4, 8]);
+
+        // Count halvings until zero: the bit length of 37 is 6.
+        let bits = Nat::unfold(37usize, |x| if x == 0 { None } else { Some(x / 2) });
+        assert_eq!(bits.to_usize(), 6);
+        // unfold(n, pred) rebuilds n itself.
+        let n = Nat::from_usize(7);
+        assert_eq!(Nat::unfold(n.clone(), |m| m.pred().cloned()), n);
+    }
+}
@@ -0,0 +1,310 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::n::Natural;
+use crate::z::Int;
+
+// Elementary number theory on top of `div_mod`, `gcd` and `mod_pow`.
+//
+// Everything is generic over the naturals backend: the Peano `Nat` is fine
+// for small inputs (and shows the algorithms are made of nothing but N), and
+// `BinNat` takes the same code to 64-bit-sized numbers and beyond.
+//
+// Functions that are undefined at 0 (factorization, divisors, ...) return None
+// there, the same way `div_mod` returns None for a zero divisor.
+
+// Trial division decides primality on its own below TRIAL_LIMIT^2.
+const TRIAL_LIMIT: usize = 1000;
+
+// Miller–Rabin witnesses: the first 13 primes. Deterministic for every
+// n < 3.3 * 10^24 (Sorenson & Webster); above that the test is a strong
+// probable-prime test.
+const WITNESSES: [usize; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+pub fn is_prime<N: Natural>(n: &N) -> bool {
+    let two = N::from_usize(2);
+    if *n < two {
+        return false;
+    }
+    // Trial division by small numbers; complete when n < TRIAL_LIMIT^2.
+    let limit = N::from_usize(TRIAL_LIMIT);
+    let mut d = two;
+    while d < limit {
+        if d.mul(&d) > *n {
+            return true;
+        }
+        if divides(&d, n) {
+            return false;
+        }
+        d = d.succ();
+    }
+    miller_rabin(n)
+}
+
+// n > 2, odd, with no small factors.
+fn miller_rabin<N: Natural>(n: &N) -> bool {
+    let one = N::one();
+    let two = N::from_usize(2);
+    let n_minus_1 = n.sub_unchecked_gte(&one);
+
+    // n - 1 = d * 2^s with d odd.
+    let mut d = n_minus_1.clone();
+    let mut s = 0usize;
+    while divides(&two, &d) {
+        d = d.div_exact(&two).expect("d is even");
+        s += 1;
+    }
+
+    'witness: for &a in WITNESSES.iter() {
+        let a = N::from_usize(a);
+        if a >= *n {
+            continue;
+        }
+        let mut x = a.mod_pow(&d, n).expect("n != 0");
+        if x == one || x == n_minus_1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.mul(&x).div_mod(n).expect("n != 0").1;
+            if x == n_minus_1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+// The prime factorization as (prime, exponent) pairs, primes ascending.
+// 1 gives the empty product; 0 has no factorization.
+pub fn factorize<N: Natural>(n: &N) -> Option<Vec<(N, usize)>> {
+    if n.is_zero() {
+        return None;
+    }
+    let mut primes = Vec::new();
+    let mut rest = n.clone();
+
+    // Peel off small factors by trial division.
+    let limit = N::from_usize(TRIAL_LIMIT);
+    let mut d = N::from_usize(2);
+    while d < limit && d.mul(&d) <= rest {
+        while let Some(q) = rest.div_exact(&d) {
+            primes.push(d.clone());
+            rest = q;
+        }
+        d = d.succ();
+    }
+    // What is left has no factor below d; split it with Pollard's rho.
+    if rest > N::one() {
+        split_large(&rest, &mut primes);
+    }
+
+    primes.sort();
+    let mut out: Vec<(N, usize)> = Vec::new();
+    for p in primes {
+        match out.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => out.push((p, 1)),
+        }
+    }
+    Some(out)
+}
+
+fn split_large<N: Natural>(n: &N, out: &mut Vec<N>) {
+    if *n == N::one() {
+        return;
+    }
+    if is_prime(n) {
+        out.push(n.clone());
+        return;
+    }
+    let d = pollard_rho(n);
+    split_large(&d, out);
+    split_large(&n.div_exact(&d).expect("d divides n"), out);
+}
+
+// A nontrivial factor of composite n: iterate x -> x^2 + c (mod n) at two
+// speeds and look for a collision modulo an unknown prime factor.
+fn pollard_rho<N: Natural>(n: &N) -> N {
+    let one = N::one();
+    let step = |x: &N, c: &N| x.mul(x).add(c).div_mod(n).expect("n != 0").1;
+    let mut c = one.clone();
+    loop {
+        let mut x = N::from_usize(2);
+        let mut y = x.clone();
+        loop {
+            x = step(&x, &c);
+            y = step(&step(&y, &c), &c);
+            let diff = if x >= y {
+                x.sub_unchecked_gte(&y)
+            } else {
+                y.sub_unchecked_gte(&x)
+            };
+            let g = diff.gcd(n);
+            if g == *n {
+                break; // cycle closed without splitting; try another c
+            }
+            if g != one {
+                return g;
+            }
+        }
+        c = c.succ();
+    }
+}
+
+// All positive divisors, ascending. None for 0.
+pub fn divisors<N: Natural>(n: &N) -> Option<Vec<N>> {
+    let mut out = vec![N::one()];
+    for (p, e) in factorize(n)? {
+        let mut next = Vec::with_capacity(out.len() * (e + 1));
+        for d in &out {
+            let mut pk = d.clone();
+            next.push(pk.clone());
+            for _ in 0..e {
+                pk = pk.mul(&p);
+                next.push(pk.clone());
+            }
+        }
+        out = next;
+    }
+    out.sort();
+    Some(out)
+}
+
+// Euler's totient: how many of 1..=n are coprime to n.
+// phi(p^e) = p^(e-1) (p - 1), multiplicative. None for 0.
+pub fn totient<N: Natural>(n: &N) -> Option<N> {
+    let mut out = N::one();
+    for (p, e) in factorize(n)? {
+        let pm1 = p.sub_unchecked_gte(&N::one());
+        out = out.mul(&pm1).mul(&p.pow(&N::from_usize(e - 1)));
+    }
+    Some(out)
+}
+
+// Möbius function: 0 if n has a squared prime factor, otherwise (-1)^k for k
+// distinct prime factors. None for 0.
+pub fn mobius<N: Natural>(n: &N) -> Option<Int<N>> {
+    let factors = factorize(n)?;
+    if factors.iter().any(|(_, e)| *e > 1) {
+        return Some(Int::zero());
+    }
+    let one = Int::from_nat(N::one());
+    Some(if factors.len() % 2 == 0 {
+        one
+    } else {
+        one.neg()
+    })
+}
+
+// Divisor function sigma_k(n): the sum of d^k over the divisors d of n.
+// sigma_0 counts divisors, sigma_1 sums them. None for 0.
+pub fn sigma<N: Natural>(n: &N, k: &N) -> Option<N> {
+    Some(
+        divisors(n)?
+            .iter()
+            .fold(N::zero(), |acc, d| acc.add(&d.pow(k))),
+    )
+}
+
+// The primes 2, 3, 5, 7, ... as an unbounded sieve of Eratosthenes: each
+// prime p found so far sits in a min-heap keyed by its next multiple, and a
+// candidate is composite exactly when it is at the top of the heap.
+pub struct Primes<N> {
+    candidate: N,
+    composites: BinaryHeap<Reverse<(N, N)>>,
+}
+
+pub fn primes<N: Natural>() -> Primes<N> {
+    Primes {
+        candidate: N::from_usize(2),
+        composites: BinaryHeap::new(),
+    }
+}
+
+impl<N: Natural> Iterator for Primes<N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        loop {
+            let c = self.candidate.clone();
+            self.candidate = c.clone().succ();
+
+            let mut composite = false;
+            while let Some(Reverse((m, _))) = self.composites.peek() {
+                if *m != c {
+                    break;
+                }
+                let Reverse((m, p)) = self.composites.pop().expect("peeked");
+                self.composites.push(Reverse((m.add(&p), p)));
+                composite = true;
+            }
+            if !composite {
+                // Smaller multiples of c were already crossed off by smaller primes.
+                self.composites.push(Reverse((c.mul(&c), c.clone())));
+                return Some(c);
+            }
+        }
+    }
+}
+
+fn divides<N: Natural>(d: &N, n: &N) -> bool {
+    n.div_mod(d).is_some_and(|(_, r)| r.is_zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::{BinNat, Nat};
+
+    #[test]
+    fn small_primes_on_peano_nat() {
+        let sieved: Vec<usize> = primes::<Nat>().take(15).map(|p| p.to_usize()).collect();
+        assert_eq!(
+            sieved,
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]
+        );
+        let tested: Vec<usize> = (0..48).filter(|&v| is_prime(&Nat::from_usize(v))).collect();
+        assert_eq!(tested, sieved);
+    }
+
+    #[test]
+    fn arithmetic_functions() {
+        let n = Nat::from_usize;
+        let f = factorize(&n(360)).unwrap();
+        let f: Vec<(usize, usize)> = f.iter().map(|(p, e)| (p.to_usize(), *e)).collect();
+        assert_eq!(f, vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(&n(1)), Some(vec![]));
+        assert_eq!(factorize(&n(0)), None);
+
+        let d: Vec<usize> = divisors(&n(12))
+            .unwrap()
+            .iter()
+            .map(|d| d.to_usize())
+            .collect();
+        assert_eq!(d, vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(totient(&n(36)), Some(n(12)));
+        assert_eq!(totient(&n(1)), Some(n(1)));
+        assert_eq!(mobius(&n(30)), Some(Int::from_nat(n(1)).neg()));
+        assert_eq!(mobius(&n(12)), Some(Int::zero()));
+        assert_eq!(mobius(&n(1)), Some(Int::from_nat(n(1))));
+        assert_eq!(sigma(&n(12), &n(0)), Some(n(6)));
+        assert_eq!(sigma(&n(12), &n(1)), Some(n(28)));
+        assert_eq!(sigma(&n(4), &n(2)), Some(n(21)));
+        assert_eq!(divisors(&n(0)), None);
+    }
+
+    #[test]
+    fn large_inputs_on_binary_backend() {
+        let b = |s: &str| s.parse::<BinNat>().unwrap();
+        assert!(is_prime(&b("2305843009213693951"))); // 2^61 - 1
+        // Strong pseudoprime to every base up to 23; only the later witnesses catch it.
+        assert!(!is_prime(&b("3825123056546413051")));
+        assert!(!is_prime(&b("561"))); // Carmichael number
+
+        // 2^64 + 1 = 274177 * 67280421310721, both factors beyond trial division.
+        let f = factorize(&b("18446744073709551617")).unwrap();
+        assert_eq!(f, vec![(b("274177"), 1), (b("67280421310721"), 1)]);
+        assert_eq!(totient(&b("1000000007")), Some(b("1000000006")));
+    }
+}
@@ -0,0 +1,107 @@
+// Macros that hang `std::ops` impls on the named methods of each stage.
+//
+// Every stage already has `add`/`sub`/`mul`/... taking `&self, &other`. These
+// macros forward an operator to such a method for all four owned/borrowed
+// operand combinations, plus the `*Assign` forms, so `a + b`, `&a + b`,
+// `a + &b`, `&a + &b` and `a += b` all work. The first argument is the list of
+// generic parameters for the impl, e.g. `[N: Natural]` (or `[]`).
+
+macro_rules! forward_binop {
+    ([$($gen:tt)*] $T:ty, $Op:ident::$op:ident, $OpAssign:ident::$op_assign:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl<$($gen)*> std::ops::$Op<&$T> for &$T {
+            type Output = $T;
+
+            fn $op(self, rhs: &$T) -> $T {
+                let ($a, $b) = (self, rhs);
+                $body
+            }
+        }
+
+        impl<$($gen)*> std::ops::$Op<$T> for $T {
+            type Output = $T;
+
+            fn $op(self, rhs: $T) -> $T {
+                std::ops::$Op::$op(&self, &rhs)
+            }
+        }
+
+        impl<$($gen)*> std::ops::$Op<&$T> for $T {
+            type Output = $T;
+
+            fn $op(self, rhs: &$T) -> $T {
+                std::ops::$Op::$op(&self, rhs)
+            }
+        }
+
+        impl<$($gen)*> std::ops::$Op<$T> for &$T {
+            type Output = $T;
+
+            fn $op(self, rhs: $T) -> $T {
+                std::ops::$Op::$op(self, &rhs)
+            }
+        }
+
+        impl<$($gen)*> std::ops::$OpAssign<$T> for $T {
+            fn $op_assign(&mut self, rhs: $T) {
+                *self = std::ops::$Op::$op(&*self, &rhs);
+            }
+        }
+
+        impl<$($gen)*> std::ops::$OpAssign<&$T> for $T {
+            fn $op_assign(&mut self, rhs: &$T) {
+                *self = std::ops::$Op::$op(&*self, rhs);
+            }
+        }
+    };
+}
+
+macro_rules! forward_neg {
+    ([$($gen:tt)*] $T:ty, |$a:ident| $body:expr) => {
+        impl<$($gen)*> std::ops::Neg for &$T {
+            type Output = $T;
+
+            fn neg(self) -> $T {
+                let $a = self;
+                $body
+            }
+        }
+
+        impl<$($gen)*> std::ops::Neg for $T {
+            type Output = $T;
+
+            fn neg(self) -> $T {
+                std::ops::Neg::neg(&self)
+            }
+        }
+    };
+}
+
+// `Sum` and `Product` over owned and borrowed items, folding with `add`/`mul`
+// from the given zero and one.
+macro_rules! forward_sum_product {
+    ([$($gen:tt)*] $T:ty, zero = $zero:expr, one = $one:expr) => {
+        impl<$($gen)*> std::iter::Sum for $T {
+            fn sum<I: Iterator<Item = $T>>(iter: I) -> $T {
+                iter.fold($zero, |acc, x| std::ops::Add::add(&acc, &x))
+            }
+        }
+
+        impl<'a, $($gen)*> std::iter::Sum<&'a $T> for $T {
+            fn sum<I: Iterator<Item = &'a $T>>(iter: I) -> $T {
+                iter.fold($zero, |acc, x| std::ops::Add::add(&acc, x))
+            }
+        }
+
+        impl<$($gen)*> std::iter::Product for $T {
+            fn product<I: Iterator<Item = $T>>(iter: I) -> $T {
+                iter.fold($one, |acc, x| std::ops::Mul::mul(&acc, &x))
+            }
+        }
+
+        impl<'a, $($gen)*> std::iter::Product<&'a $T> for $T {
+            fn product<I: Iterator<Item = &'a $T>>(iter: I) -> $T {
+                iter.fold($one, |acc, x| std::ops::Mul::mul(&acc, x))
+            }
+        }
+    };
+}
@@ -1,35 +1,53 @@
 use std::cmp::Ordering;
 use std::fmt;
 
-use crate::n::Nat;
+use crate::n::{Nat, Natural};
 use crate::z::Int;
 
+mod cf;
+mod decimal;
+mod egyptian;
+mod enumerate;
+mod farey;
+mod parse;
+mod pow;
+mod round;
+
+pub use decimal::Expansion;
+pub use egyptian::EgyptianMethod;
+pub use enumerate::{
+    CalkinWilf, SternBrocot, calkin_wilf, int_to_nat, nat_to_int, nat_to_rat, rat_to_nat,
+    stern_brocot,
+};
+pub use farey::{FordCircle, farey, ford_circles_csv};
+pub use parse::{ParseRatError, ParseRatErrorKind};
+
 // Rationals (Q) built on top of Z and N (positive denominator).
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Rat {
-    pub num: Int,
-    pub den: Nat, // invariant: den != 0
+pub struct Rat<N = Nat> {
+    pub num: Int<N>,
+    pub den: N, // invariant: den != 0
 }
 
-impl Rat {
-    pub fn new(num: Int, den: Nat) -> Option<Rat> {
+impl<N: Natural> Rat<N> {
+    pub fn new(num: Int<N>, den: N) -> Option<Rat<N>> {
         if den.is_zero() {
             return None;
         }
         Some(Rat { num, den }.normalized())
     }
 
-    pub fn zero() -> Rat {
+    pub fn zero() -> Rat<N> {
         Rat {
             num: Int::Zero,
-            den: Nat::one(),
+            den: N::one(),
         }
     }
 
-    pub fn one() -> Rat {
+    pub fn one() -> Rat<N> {
         Rat {
-            num: Int::from_nat(Nat::one()),
-            den: Nat::one(),
+            num: Int::from_nat(N::one()),
+            den: N::one(),
         }
     }
 
@@ -41,11 +59,11 @@ impl Rat {
         self.num.is_negative()
     }
 
-    pub fn from_int(i: Int) -> Rat {
-        Rat::new(i, Nat::one()).expect("den != 0")
+    pub fn from_int(i: Int<N>) -> Rat<N> {
+        Rat::new(i, N::one()).expect("den != 0")
     }
 
-    pub fn abs(&self) -> Rat {
+    pub fn abs(&self) -> Rat<N> {
         if self.is_negative() {
             self.neg()
         } else {
@@ -53,14 +71,14 @@ impl Rat {
         }
     }
 
-    fn normalized(mut self) -> Rat {
+    fn normalized(mut self) -> Rat<N> {
         if self.num.is_zero() {
-            self.den = Nat::one();
+            self.den = N::one();
             return self;
         }
 
         let g = self.num.abs_nat().gcd(&self.den);
-        if g.is_zero() || g == Nat::one() {
+        if g.is_zero() || g == N::one() {
             return self;
         }
 
@@ -79,14 +97,14 @@ impl Rat {
         }
     }
 
-    pub fn neg(&self) -> Rat {
+    pub fn neg(&self) -> Rat<N> {
         Rat {
             num: self.num.neg(),
             den: self.den.clone(),
         }
     }
 
-    pub fn add(&self, other: &Rat) -> Rat {
+    pub fn add(&self, other: &Rat<N>) -> Rat<N> {
         // a/b + c/d = (ad + cb) / (bd)
         let a = &self.num;
         let b = &self.den;
@@ -101,18 +119,18 @@ impl Rat {
         Rat::new(num, den).expect("den != 0")
     }
 
-    pub fn sub(&self, other: &Rat) -> Rat {
+    pub fn sub(&self, other: &Rat<N>) -> Rat<N> {
         self.add(&other.neg())
     }
 
-    pub fn mul(&self, other: &Rat) -> Rat {
+    pub fn mul(&self, other: &Rat<N>) -> Rat<N> {
         // (a/b)(c/d) = (ac)/(bd)
         let num = self.num.mul(&other.num);
         let den = self.den.mul(&other.den);
         Rat::new(num, den).expect("den != 0")
     }
 
-    pub fn div(&self, other: &Rat) -> Option<Rat> {
+    pub fn div(&self, other: &Rat<N>) -> Option<Rat<N>> {
         // (a/b)/(c/d) = (a*d)/(b*c)
         if other.num.is_zero() {
             return None;
@@ -130,7 +148,7 @@ impl Rat {
         Rat::new(num, den)
     }
 
-    pub fn cmp_rat(&self, other: &Rat) -> Ordering {
+    pub fn cmp_rat(&self, other: &Rat<N>) -> Ordering {
         // Compare a/b and c/d by comparing ad and cb (denominators are > 0).
         let a = &self.num;
         let b = &self.den;
@@ -143,21 +161,21 @@ impl Rat {
     }
 }
 
-impl PartialOrd for Rat {
+impl<N: Natural> PartialOrd for Rat<N> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp_rat(other))
+        Some(self.cmp(other))
     }
 }
 
-impl Ord for Rat {
+impl<N: Natural> Ord for Rat<N> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.cmp_rat(other)
     }
 }
 
-impl fmt::Display for Rat {
+impl<N: Natural> fmt::Display for Rat<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.den == Nat::one() {
+        if self.den == N::one() {
             write!(f, "{}", self.num)
         } else {
             write!(f, "{}/{}", self.num, self.den)
@@ -165,6 +183,29 @@ impl fmt::Display for Rat {
     }
 }
 
+// Operators. `/` panics on a zero divisor; `div` is the checked form.
+forward_binop!([N: Natural] Rat<N>, Add::add, AddAssign::add_assign, |a, b| Rat::add(a, b));
+forward_binop!([N: Natural] Rat<N>, Sub::sub, SubAssign::sub_assign, |a, b| Rat::sub(a, b));
+forward_binop!([N: Natural] Rat<N>, Mul::mul, MulAssign::mul_assign, |a, b| Rat::mul(a, b));
+forward_binop!([N: Natural] Rat<N>, Div::div, DivAssign::div_assign, |a, b| {
+    Rat::div(a, b).expect("Rat division by zero")
+});
+forward_neg!([N: Natural] Rat<N>, |a| Rat::neg(a));
+forward_sum_product!([N: Natural] Rat<N>, zero = Rat::zero(), one = Rat::one());
+
+// Test helpers shared by the Q submodules.
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::Rat;
+    use crate::n::Nat;
+    pub(crate) use crate::z::test_util::int;
+
+    // num/den on the Peano backend, reduced.
+    pub(crate) fn rat(num: isize, den: usize) -> Rat {
+        Rat::new(int(num), Nat::from_usize(den)).unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,4 +222,38 @@ mod tests {
         let s = one.add(&three);
         assert_eq!(format!("{}", s), "5/6");
     }
+
+    #[test]
+    fn rat_over_binary_backend() {
+        use crate::n::BinNat;
+
+        let third = Rat::new(Int::from_nat(BinNat::one()), BinNat::from_usize(3)).unwrap();
+        let sixth = Rat::new(Int::from_nat(BinNat::one()), BinNat::from_usize(6)).unwrap();
+        assert_eq!(format!("{}", third.add(&sixth)), "1/2");
+        assert_eq!(format!("{}", third.sub(&third.add(&third))), "-1/3");
+    }
+
+    #[test]
+    fn rat_operators() {
+        let half = Rat::new(Int::from_nat(Nat::one()), Nat::from_usize(2)).unwrap();
+        let third = Rat::new(Int::from_nat(Nat::one()), Nat::from_usize(3)).unwrap();
+        assert_eq!(format!("{}", &half - &third), "1/6");
+        assert_eq!(format!("{}", &half / &third), "3/2");
+        assert_eq!(format!("{}", -(&half * &third)), "-1/6");
+
+        let mut x = half.clone();
+        x /= &third;
+        x -= Rat::one();
+        assert_eq!(x, half);
+
+        let parts = [half.clone(), third.clone(), half.clone()];
+        assert_eq!(format!("{}", parts.iter().sum::<Rat>()), "4/3");
+        assert_eq!(format!("{}", parts.into_iter().product::<Rat>()), "1/12");
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn rat_div_operator_panics_on_zero() {
+        let _ = Rat::<Nat>::one() / Rat::zero();
+    }
 }
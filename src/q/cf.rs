@@ -0,0 +1,208 @@
+use crate::n::Natural;
+use crate::z::{Int, Rounding};
+
+use super::Rat;
+
+// Continued fractions. Every rational has a finite expansion
+//   x = a0 + 1/(a1 + 1/(a2 + ... + 1/an))
+// written [a0; a1, ..., an], where a0 = floor(x) and the later terms are
+// positive. It is Euclid's algorithm on (num, den), keeping the quotients.
+//
+// Truncating the expansion gives the convergents p_k/q_k:
+//   p_k = a_k p_{k-1} + p_{k-2},   p_{-1} = 1, p_{-2} = 0
+//   q_k = a_k q_{k-1} + q_{k-2},   q_{-1} = 0, q_{-2} = 1
+// They alternate around x and are the best approximations "of the second
+// kind". Stepping the last term from 1 up to a_k instead gives the
+// semiconvergents (p_{k-2} + j p_{k-1}) / (q_{k-2} + j q_{k-1}), and every
+// best approximation with a bounded denominator is one of those.
+impl<N: Natural> Rat<N> {
+    // The canonical expansion: the last term is > 1 unless it is the only one.
+    pub fn to_continued_fraction(&self) -> Vec<Int<N>> {
+        let mut out = Vec::new();
+        let mut num = self.num.clone();
+        let mut den = Int::from_nat(self.den.clone());
+        loop {
+            let (a, r) = num.div_rem(&den, Rounding::Floor).expect("den != 0");
+            out.push(a);
+            if r.is_zero() {
+                return out;
+            }
+            num = std::mem::replace(&mut den, r);
+        }
+    }
+
+    // Evaluate [a0; a1, ..., an] from the inside out. Any terms are accepted,
+    // not only canonical ones. None if `terms` is empty or some tail
+    // evaluates to zero, leaving 1/0.
+    pub fn from_continued_fraction(terms: &[Int<N>]) -> Option<Rat<N>> {
+        let (last, rest) = terms.split_last()?;
+        let mut x = Rat::from_int(last.clone());
+        for a in rest.iter().rev() {
+            x = Rat::from_int(a.clone()).add(&Rat::one().div(&x)?);
+        }
+        Some(x)
+    }
+
+    // p_0/q_0, p_1/q_1, ..., ending with self.
+    pub fn convergents(&self) -> impl Iterator<Item = Rat<N>> {
+        let pq = convergent_pairs(&self.to_continued_fraction());
+        pq.into_iter().skip(1).map(|(p, q)| to_rat(p, q))
+    }
+
+    // a0, then for each later term a_k the fractions with j = 1, ..., a_k
+    // (j = a_k is the convergent itself), ending with self. Increasing
+    // denominators throughout.
+    pub fn semiconvergents(&self) -> impl Iterator<Item = Rat<N>> {
+        let terms = self.to_continued_fraction();
+        let pq = convergent_pairs(&terms);
+        let mut out = vec![to_rat(pq[1].0.clone(), pq[1].1.clone())];
+        let one = Int::from_nat(N::one());
+        for (k, a) in terms.iter().enumerate().skip(1) {
+            // pq[k] is p_{k-1}/q_{k-1}, pq[k - 1] is p_{k-2}/q_{k-2}.
+            let (p1, q1) = &pq[k];
+            let (mut p, mut q) = pq[k - 1].clone();
+            let mut j = Int::zero();
+            while j < *a {
+                (p, q) = (p.add(p1), q.add(q1));
+                j = j.add(&one);
+                out.push(to_rat(p.clone(), q.clone()));
+            }
+        }
+        out.into_iter()
+    }
+
+    // The fraction closest to self among those with denominator <= max_den;
+    // on a tie, the one with the smaller denominator. None if max_den == 0.
+    pub fn best_approximation(&self, max_den: &N) -> Option<Rat<N>> {
+        if max_den.is_zero() {
+            return None;
+        }
+        if self.den <= *max_den {
+            return Some(self.clone());
+        }
+        let terms = self.to_continued_fraction();
+        let pq = convergent_pairs(&terms);
+        let limit = Int::from_nat(max_den.clone());
+
+        // The last convergent p_n/q_n with q_n <= max_den. q_0 = 1 always fits,
+        // and self does not, so n + 1 is a valid index into `terms`.
+        let n = (1..pq.len())
+            .take_while(|&i| pq[i].1 <= limit)
+            .last()
+            .expect("q_0 = 1")
+            - 1;
+        let (pn, qn) = &pq[n + 1];
+        let (pm, qm) = &pq[n];
+        let best_conv = to_rat(pn.clone(), qn.clone());
+
+        // The largest semiconvergent towards p_{n+1}/q_{n+1} that still fits:
+        // j = floor((max_den - q_{n-1}) / q_n), and j < a_{n+1}.
+        let (j, _) = limit
+            .sub(qm)
+            .div_rem(qn, Rounding::Floor)
+            .expect("q_n != 0");
+        if j.is_zero() {
+            return Some(best_conv);
+        }
+        let semi = to_rat(pm.add(&j.mul(pn)), qm.add(&j.mul(qn)));
+        let d_conv = self.sub(&best_conv).abs();
+        let d_semi = self.sub(&semi).abs();
+        Some(if d_semi < d_conv { semi } else { best_conv })
+    }
+}
+
+// [(p_{-1}, q_{-1}), (p_0, q_0), ..., (p_n, q_n)]: entry k is p_{k-1}/q_{k-1}.
+fn convergent_pairs<N: Natural>(terms: &[Int<N>]) -> Vec<(Int<N>, Int<N>)> {
+    let one = Int::from_nat(N::one());
+    let mut out = vec![(one.clone(), Int::zero())];
+    let (mut p2, mut q2) = (Int::zero(), one);
+    for a in terms {
+        let (p1, q1) = out.last().expect("nonempty").clone();
+        let next = (a.mul(&p1).add(&p2), a.mul(&q1).add(&q2));
+        (p2, q2) = (p1, q1);
+        out.push(next);
+    }
+    out
+}
+
+// q > 0 for every convergent of a canonical expansion.
+fn to_rat<N: Natural>(p: Int<N>, q: Int<N>) -> Rat<N> {
+    Rat::new(p, q.abs_nat()).expect("q > 0")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::{BinNat, Nat};
+    use crate::q::test_util::{int, rat};
+
+    fn show(xs: impl Iterator<Item = Rat>) -> Vec<String> {
+        xs.map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn expansion_round_trips() {
+        let cf = |x: Rat| -> Vec<String> {
+            x.to_continued_fraction()
+                .iter()
+                .map(|a| a.to_string())
+                .collect()
+        };
+        assert_eq!(cf(rat(415, 93)), vec!["4", "2", "6", "7"]);
+        assert_eq!(cf(rat(-7, 3)), vec!["-3", "1", "2"]);
+        assert_eq!(cf(rat(5, 1)), vec!["5"]);
+        assert_eq!(cf(rat(1, 3)), vec!["0", "3"]);
+
+        for (n, d) in [(415, 93), (-7, 3), (0, 1), (1, 3), (-22, 7), (13, 8)] {
+            let x = rat(n, d);
+            assert_eq!(
+                Rat::from_continued_fraction(&x.to_continued_fraction()),
+                Some(x)
+            );
+        }
+        // Non-canonical input: [0; 1, 1] = 1/2, and [1; 0] divides by zero.
+        assert_eq!(
+            Rat::from_continued_fraction(&[int(0), int(1), int(1)]),
+            Some(rat(1, 2))
+        );
+        assert_eq!(Rat::from_continued_fraction(&[int(1), int(0)]), None);
+        assert_eq!(Rat::<Nat>::from_continued_fraction(&[]), None);
+    }
+
+    #[test]
+    fn convergents_and_semiconvergents() {
+        let x = rat(415, 93);
+        assert_eq!(show(x.convergents()), vec!["4", "9/2", "58/13", "415/93"]);
+        let semi = show(x.semiconvergents());
+        assert_eq!(
+            semi[..9],
+            [
+                "4", "5", "9/2", "13/3", "22/5", "31/7", "40/9", "49/11", "58/13"
+            ]
+        );
+        assert_eq!(semi.len(), 1 + 2 + 6 + 7);
+        assert_eq!(semi.last().map(String::as_str), Some("415/93"));
+        assert_eq!(show(rat(-7, 3).convergents()), vec!["-3", "-2", "-7/3"]);
+    }
+
+    #[test]
+    fn best_approximations() {
+        let n = Nat::from_usize;
+        assert_eq!(rat(415, 93).best_approximation(&n(10)), Some(rat(40, 9)));
+        assert_eq!(rat(415, 93).best_approximation(&n(100)), Some(rat(415, 93)));
+        assert_eq!(rat(-7, 3).best_approximation(&n(1)), Some(rat(-2, 1)));
+        assert_eq!(rat(1, 3).best_approximation(&n(2)), Some(rat(1, 2)));
+        assert_eq!(rat(1, 3).best_approximation(&n(0)), None);
+
+        // A measured value of pi, on the binary backend.
+        let b = |s: &str| s.parse::<BinNat>().unwrap();
+        let pi = Rat::new(Int::from_nat(b("3141592653")), b("1000000000")).unwrap();
+        let approx = |d: &str| pi.best_approximation(&b(d)).unwrap().to_string();
+        assert_eq!(approx("1"), "3");
+        assert_eq!(approx("7"), "22/7");
+        assert_eq!(approx("57"), "179/57");
+        assert_eq!(approx("100"), "311/99");
+        assert_eq!(approx("1000"), "355/113");
+        assert_eq!(approx("100000"), "103993/33102");
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::n::Natural;
+use crate::z::{Int, Rounding};
+
+use super::Rat;
+
+// Positional expansions of rationals. Long division of the fractional part
+// either terminates or, since there are only den possible remainders, falls
+// into a cycle; remembering where each remainder first appeared tells us
+// exactly where the repeating block starts. So every rational prints exactly:
+//   1/8 = 0.125    1/7 = 0.(142857)    1/6 = 0.1(6)    -22/7 = -3.(142857)
+
+// A rational written in some radix: [-]int_part.pre_period(period).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expansion<N> {
+    pub negative: bool,
+    pub int_part: N,
+    // Digits after the point before the cycle starts.
+    pub pre_period: Vec<u32>,
+    // The repeating block; empty when the expansion terminates.
+    pub period: Vec<u32>,
+    pub radix: u32,
+}
+
+impl<N: Natural> Rat<N> {
+    // The exact expansion in base `radix`. None unless 2 <= radix <= 36.
+    pub fn expansion(&self, radix: u32) -> Option<Expansion<N>> {
+        if !(2..=36).contains(&radix) {
+            return None;
+        }
+        let base = N::from_usize(radix as usize);
+        let (int_part, mut rem) = self.num.abs_nat().div_mod(&self.den).expect("den != 0");
+
+        // seen[r] = index of the digit produced from remainder r.
+        let mut seen = BTreeMap::new();
+        let mut digits = Vec::new();
+        while !rem.is_zero() {
+            if let Some(&start) = seen.get(&rem) {
+                let period = digits.split_off(start);
+                return Some(Expansion {
+                    negative: self.is_negative(),
+                    int_part,
+                    pre_period: digits,
+                    period,
+                    radix,
+                });
+            }
+            seen.insert(rem.clone(), digits.len());
+            let (d, r) = rem.mul(&base).div_mod(&self.den).expect("den != 0");
+            digits.push(d.to_usize() as u32);
+            rem = r;
+        }
+        Some(Expansion {
+            negative: self.is_negative(),
+            int_part,
+            pre_period: digits,
+            period: Vec::new(),
+            radix,
+        })
+    }
+
+    // "0.(142857)", "0.1(6)", "-1.25", "3".
+    pub fn to_decimal_string(&self) -> String {
+        self.expansion(10).expect("10 is a valid radix").to_string()
+    }
+
+    // Like `to_decimal_string` in another base. None unless 2 <= radix <= 36.
+    pub fn to_string_radix(&self, radix: u32) -> Option<String> {
+        Some(self.expansion(radix)?.to_string())
+    }
+
+    // Exactly `digits` decimal places, rounded as asked: 2/3 with 3 digits is
+    // "0.667" under `Rounding::HalfEven` and "0.666" under `Rounding::Trunc`.
+    pub fn to_fixed_string(&self, digits: usize, rounding: Rounding) -> String {
+        self.to_fixed_string_radix(digits, rounding, 10)
+            .expect("10 is a valid radix")
+    }
+
+    // Fixed places in base `radix`. None unless 2 <= radix <= 36.
+    pub fn to_fixed_string_radix(
+        &self,
+        digits: usize,
+        rounding: Rounding,
+        radix: u32,
+    ) -> Option<String> {
+        if !(2..=36).contains(&radix) {
+            return None;
+        }
+        // Round self * radix^digits to an integer, then put the point back.
+        let scale = N::from_usize(radix as usize).pow(&N::from_usize(digits));
+        let q = self
+            .mul(&Rat::from_int(Int::from_nat(scale)))
+            .round(rounding);
+
+        let mut s = to_radix(&q.abs_nat(), radix);
+        if s.len() <= digits {
+            s.insert_str(0, &"0".repeat(digits + 1 - s.len()));
+        }
+        if digits > 0 {
+            s.insert(s.len() - digits, '.');
+        }
+        if q.is_negative() {
+            s.insert(0, '-');
+        }
+        Some(s)
+    }
+}
+
+impl<N: Natural> fmt::Display for Expansion<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digit = |d: &u32| char::from_digit(*d, self.radix).expect("digit < radix");
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", to_radix(&self.int_part, self.radix))?;
+        if self.pre_period.is_empty() && self.period.is_empty() {
+            return Ok(());
+        }
+        write!(f, ".")?;
+        for d in &self.pre_period {
+            write!(f, "{}", digit(d))?;
+        }
+        if !self.period.is_empty() {
+            write!(f, "(")?;
+            for d in &self.period {
+                write!(f, "{}", digit(d))?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+// n written in base radix (2..=36), lowercase digits.
+fn to_radix<N: Natural>(n: &N, radix: u32) -> String {
+    let base = N::from_usize(radix as usize);
+    let mut digits = Vec::new();
+    let mut rest = n.clone();
+    loop {
+        let (q, d) = rest.div_mod(&base).expect("radix != 0");
+        digits.push(char::from_digit(d.to_usize() as u32, radix).expect("digit < radix"));
+        if q.is_zero() {
+            break;
+        }
+        rest = q;
+    }
+    digits.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::BinNat;
+    use crate::q::test_util::rat;
+
+    #[test]
+    fn detects_pre_period_and_period() {
+        let dec = |n, d| rat(n, d).to_decimal_string();
+        assert_eq!(dec(1, 7), "0.(142857)");
+        assert_eq!(dec(1, 6), "0.1(6)");
+        assert_eq!(dec(1, 12), "0.08(3)");
+        assert_eq!(dec(-22, 7), "-3.(142857)");
+        assert_eq!(dec(-1, 3), "-0.(3)");
+        assert_eq!(dec(-5, 4), "-1.25");
+        assert_eq!(dec(3, 1), "3");
+        assert_eq!(dec(0, 1), "0");
+
+        let e = rat(1, 6).expansion(10).unwrap();
+        assert_eq!((e.pre_period, e.period), (vec![1], vec![6]));
+    }
+
+    #[test]
+    fn other_radices() {
+        assert_eq!(rat(1, 3).to_string_radix(2).as_deref(), Some("0.(01)"));
+        assert_eq!(rat(1, 10).to_string_radix(2).as_deref(), Some("0.0(0011)"));
+        assert_eq!(rat(255, 16).to_string_radix(16).as_deref(), Some("f.f"));
+        assert_eq!(rat(1, 3).to_string_radix(1), None);
+        assert_eq!(rat(1, 3).to_string_radix(37), None);
+        assert_eq!(
+            rat(5, 2)
+                .to_fixed_string_radix(3, Rounding::Trunc, 2)
+                .as_deref(),
+            Some("10.100")
+        );
+    }
+
+    #[test]
+    fn fixed_digits_with_rounding() {
+        let fixed = |n, d, digits, r| rat(n, d).to_fixed_string(digits, r);
+        assert_eq!(fixed(2, 3, 3, Rounding::HalfEven), "0.667");
+        assert_eq!(fixed(2, 3, 3, Rounding::Trunc), "0.666");
+        assert_eq!(fixed(1, 8, 2, Rounding::HalfEven), "0.12");
+        assert_eq!(fixed(1, 8, 2, Rounding::HalfAwayFromZero), "0.13");
+        assert_eq!(fixed(-1, 8, 2, Rounding::HalfEven), "-0.12");
+        assert_eq!(fixed(-1, 8, 2, Rounding::HalfAwayFromZero), "-0.13");
+        assert_eq!(fixed(-1, 8, 2, Rounding::HalfUp), "-0.12");
+        assert_eq!(fixed(-1, 8, 2, Rounding::Floor), "-0.13");
+        assert_eq!(fixed(-1, 1000, 2, Rounding::HalfEven), "0.00");
+        assert_eq!(fixed(5, 2, 0, Rounding::HalfEven), "2");
+        assert_eq!(fixed(7, 1, 2, Rounding::Trunc), "7.00");
+    }
+
+    #[test]
+    fn long_periods_on_binary_backend() {
+        // 1/97 has the full period 96 in base 10.
+        let x = Rat::new(Int::from_nat(BinNat::one()), BinNat::from_usize(97)).unwrap();
+        let e = x.expansion(10).unwrap();
+        assert!(e.pre_period.is_empty());
+        assert_eq!(e.period.len(), 96);
+        assert!(
+            x.to_decimal_string()
+                .starts_with("0.(010309278350515463917525773195876288659793814432")
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::n::Natural;
+use crate::z::Int;
+
+use super::Rat;
+
+// Egyptian fractions: writing x > 0 as a sum of distinct unit fractions
+// 1/d1 + 1/d2 + ... . Decompositions are returned as the denominators,
+// ascending.
+//
+// Above 1, the harmonic terms 1/1 + 1/2 + ... + 1/m are taken while they
+// fit. The harmonic series diverges, so any x is reached, but only after
+// about e^(x - 0.58) terms, so this is meant for modest x. The remainder is
+// then below 1/(m+1), so each of its unit fractions has a denominator
+// greater than m + 1 and none repeats one already taken. The methods below
+// decompose that remainder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EgyptianMethod {
+    // Fibonacci–Sylvester: repeatedly take the largest unit fraction that
+    // fits. Always terminates (the numerator strictly drops), but the
+    // denominators can explode: 5/121 ends in a 25-digit denominator.
+    Greedy,
+    // Golomb: with c = a^-1 (mod b) and ac = 1 + kb,
+    //   a/b = k/c + 1/(bc),
+    // then recurse on k/c (k < a, c < b). Every denominator is at most
+    // b(b - 1), so 5/121 stays below 14520.
+    Golomb,
+}
+
+impl<N: Natural> Rat<N> {
+    // The denominators of a decomposition of self. None unless self > 0.
+    pub fn egyptian_fractions(&self, method: EgyptianMethod) -> Option<Vec<N>> {
+        if !matches!(self.num, Int::Pos(_)) {
+            return None;
+        }
+        let mut rest = self.clone();
+        let mut out = Vec::new();
+        let mut d = N::one();
+        loop {
+            let unit = Rat::new(Int::from_nat(N::one()), d.clone()).expect("d != 0");
+            if unit > rest {
+                break;
+            }
+            rest = rest.sub(&unit);
+            out.push(d.clone());
+            d = d.succ();
+        }
+        if let Int::Pos(a) = &rest.num {
+            out.extend(match method {
+                EgyptianMethod::Greedy => greedy(a.clone(), rest.den.clone()),
+                EgyptianMethod::Golomb => golomb(a.clone(), rest.den.clone()),
+            });
+        }
+        out.sort();
+        Some(out)
+    }
+
+    // True if `denominators` are distinct, nonzero, and 1/d1 + 1/d2 + ...
+    // adds up to self exactly.
+    pub fn is_egyptian_decomposition(&self, denominators: &[N]) -> bool {
+        let mut sorted = denominators.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        if sorted.len() != denominators.len() || sorted.iter().any(N::is_zero) {
+            return false;
+        }
+        let one = Int::from_nat(N::one());
+        let sum = denominators.iter().fold(Rat::zero(), |acc, d| {
+            acc.add(&Rat::new(one.clone(), d.clone()).expect("d != 0"))
+        });
+        sum == *self
+    }
+}
+
+fn greedy<N: Natural>(mut a: N, mut b: N) -> Vec<N> {
+    let mut out = Vec::new();
+    while !a.is_zero() {
+        // d = ceil(b / a); then a/b - 1/d = (ad - b) / (bd).
+        let (q, r) = b.div_mod(&a).expect("a != 0");
+        let d = if r.is_zero() { q } else { q.succ() };
+        let num = a.mul(&d).sub_unchecked_gte(&b);
+        let den = b.mul(&d);
+        out.push(d);
+        if num.is_zero() {
+            break;
+        }
+        let g = num.gcd(&den);
+        a = num.div_exact(&g).expect("g divides num");
+        b = den.div_exact(&g).expect("g divides den");
+    }
+    out
+}
+
+fn golomb<N: Natural>(mut a: N, mut b: N) -> Vec<N> {
+    let mut out = Vec::new();
+    // a/b stays reduced: ac - kb = 1 makes k and c coprime.
+    while a != N::one() {
+        let c = Int::from_nat(a.clone())
+            .mod_inverse(&b)
+            .expect("a and b are coprime");
+        let k = a
+            .mul(&c)
+            .sub_unchecked_gte(&N::one())
+            .div_exact(&b)
+            .expect("ac = 1 (mod b)");
+        out.push(b.mul(&c));
+        (a, b) = (k, c);
+    }
+    out.push(b);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::{BinNat, Nat};
+
+    fn rat<N: Natural>(num: usize, den: usize) -> Rat<N> {
+        Rat::new(Int::from_nat(N::from_usize(num)), N::from_usize(den)).unwrap()
+    }
+
+    fn dens<N: Natural>(x: &Rat<N>, method: EgyptianMethod) -> Vec<String> {
+        let d = x.egyptian_fractions(method).unwrap();
+        assert!(x.is_egyptian_decomposition(&d), "{} {:?}", x, method);
+        d.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn greedy_and_golomb() {
+        let x = rat::<Nat>(4, 13);
+        assert_eq!(dens(&x, EgyptianMethod::Greedy), vec!["4", "18", "468"]);
+        assert_eq!(
+            dens(&x, EgyptianMethod::Golomb),
+            vec!["4", "28", "70", "130"]
+        );
+        assert_eq!(dens(&rat::<Nat>(1, 1), EgyptianMethod::Greedy), vec!["1"]);
+        assert_eq!(dens(&rat::<Nat>(1, 7), EgyptianMethod::Golomb), vec!["7"]);
+        assert_eq!(
+            Rat::<Nat>::zero().egyptian_fractions(EgyptianMethod::Golomb),
+            None
+        );
+    }
+
+    #[test]
+    fn rationals_above_one() {
+        // 3/2 = 1 + 1/2; 5/2 = 1 + ... + 1/6 + 1/20.
+        for method in [EgyptianMethod::Greedy, EgyptianMethod::Golomb] {
+            assert_eq!(dens(&rat::<Nat>(3, 2), method), vec!["1", "2"]);
+            assert_eq!(
+                dens(&rat::<Nat>(5, 2), method),
+                vec!["1", "2", "3", "4", "5", "6", "20"]
+            );
+            assert_eq!(dens(&rat::<Nat>(2, 1), method), vec!["1", "2", "3", "6"]);
+        }
+        for num in 1..=12 {
+            for den in [3, 7, 10] {
+                let x = rat::<BinNat>(num, den);
+                dens(&x, EgyptianMethod::Greedy);
+                dens(&x, EgyptianMethod::Golomb);
+            }
+        }
+    }
+
+    #[test]
+    fn golomb_keeps_denominators_small() {
+        let x = rat::<BinNat>(5, 121);
+        assert_eq!(
+            dens(&x, EgyptianMethod::Greedy),
+            vec![
+                "25",
+                "757",
+                "763309",
+                "873960180913",
+                "1527612795642093418846225"
+            ]
+        );
+        let bound = BinNat::from_usize(121 * 120);
+        for d in x.egyptian_fractions(EgyptianMethod::Golomb).unwrap() {
+            assert!(d <= bound);
+        }
+        // Every proper fraction with a small denominator, both ways.
+        for b in 2..=30 {
+            for a in 1..b {
+                let x = rat::<BinNat>(a, b);
+                dens(&x, EgyptianMethod::Greedy);
+                dens(&x, EgyptianMethod::Golomb);
+            }
+        }
+    }
+
+    #[test]
+    fn verification_rejects_bad_decompositions() {
+        let n = Nat::from_usize;
+        let half = rat::<Nat>(1, 2);
+        assert!(half.is_egyptian_decomposition(&[n(3), n(6)]));
+        assert!(!half.is_egyptian_decomposition(&[n(4), n(4)])); // not distinct
+        assert!(!half.is_egyptian_decomposition(&[n(3), n(7)])); // wrong sum
+        assert!(!half.is_egyptian_decomposition(&[n(0), n(2)]));
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::VecDeque;
+
+use crate::n::Natural;
+use crate::z::Int;
+
+use super::Rat;
+
+// Q is countable. Two classic trees contain every positive rational exactly
+// once, already in lowest terms:
+//
+// - Stern–Brocot: start from the bounds 0/1 and 1/0; each node is the mediant
+//   (a+c)/(b+d) of its bounds, its left subtree lies between the lower bound
+//   and the node, its right subtree between the node and the upper bound.
+//   Each level, read left to right, is increasing.
+// - Calkin–Wilf: root 1/1, the children of a/b are a/(a+b) (L) and
+//   (a+b)/b (R). Read breadth-first it is the sequence 1, 1/2, 2, 1/3, 3/2, ...
+//
+// A rational's path in one tree is its path in the other, reversed. Numbering
+// the Calkin–Wilf tree breadth-first from 1 (children of i are 2i and 2i+1)
+// turns it into a bijection N+ <-> Q+, and interleaving signs extends that
+// to N <-> Q.
+
+// Calkin–Wilf order: 1, 1/2, 2, 1/3, 3/2, 2/3, 3, 1/4, ...
+pub struct CalkinWilf<N> {
+    num: N,
+    den: N,
+}
+
+pub fn calkin_wilf<N: Natural>() -> CalkinWilf<N> {
+    CalkinWilf {
+        num: N::one(),
+        den: N::one(),
+    }
+}
+
+impl<N: Natural> Iterator for CalkinWilf<N> {
+    type Item = Rat<N>;
+
+    fn next(&mut self) -> Option<Rat<N>> {
+        let out = positive(self.num.clone(), self.den.clone());
+        // Newman: the successor of x is 1 / (2 floor(x) + 1 - x), i.e. for
+        // a/b = q + r/b it is b / ((2q + 1) b - a) = b / (q b + b - r).
+        let (q, r) = self.num.div_mod(&self.den).expect("den != 0");
+        let next_den = q.mul(&self.den).add(&self.den).sub_unchecked_gte(&r);
+        self.num = std::mem::replace(&mut self.den, next_den);
+        Some(out)
+    }
+}
+
+// The Stern–Brocot tree level by level: 1; 1/2, 2; 1/3, 2/3, 3/2, 3; ...
+pub struct SternBrocot<N> {
+    // Pending nodes as their (lower, upper) bounds, each a (num, den) pair.
+    queue: VecDeque<((N, N), (N, N))>,
+}
+
+pub fn stern_brocot<N: Natural>() -> SternBrocot<N> {
+    let bounds = ((N::zero(), N::one()), (N::one(), N::zero()));
+    SternBrocot {
+        queue: VecDeque::from([bounds]),
+    }
+}
+
+impl<N: Natural> Iterator for SternBrocot<N> {
+    type Item = Rat<N>;
+
+    fn next(&mut self) -> Option<Rat<N>> {
+        let (lo, hi) = self.queue.pop_front()?;
+        let node = (lo.0.add(&hi.0), lo.1.add(&hi.1));
+        self.queue.push_back((lo, node.clone()));
+        self.queue.push_back((node.clone(), hi));
+        Some(positive(node.0, node.1))
+    }
+}
+
+impl<N: Natural> Rat<N> {
+    // The path from the root of the Stern–Brocot tree, e.g. "RRL" for 5/2;
+    // the root 1 has the empty path. None unless self > 0.
+    pub fn stern_brocot_path(&self) -> Option<String> {
+        let (mut a, mut b) = positive_parts(self)?;
+        let mut path = String::new();
+        // Going right subtracts the denominator from the numerator, going
+        // left the other way round, until we reach 1/1.
+        while a != b {
+            if a > b {
+                path.push('R');
+                a = a.sub_unchecked_gte(&b);
+            } else {
+                path.push('L');
+                b = b.sub_unchecked_gte(&a);
+            }
+        }
+        Some(path)
+    }
+
+    // Follow an L/R path down the Stern–Brocot tree. None on any other
+    // character.
+    pub fn from_stern_brocot_path(path: &str) -> Option<Rat<N>> {
+        let (mut lo, mut hi) = ((N::zero(), N::one()), (N::one(), N::zero()));
+        let mut node = (N::one(), N::one());
+        for c in path.chars() {
+            match c {
+                'L' => hi = node,
+                'R' => lo = node,
+                _ => return None,
+            }
+            node = (lo.0.add(&hi.0), lo.1.add(&hi.1));
+        }
+        Some(positive(node.0, node.1))
+    }
+
+    // The path from the root of the Calkin–Wilf tree. None unless self > 0.
+    pub fn calkin_wilf_path(&self) -> Option<String> {
+        let (mut a, mut b) = positive_parts(self)?;
+        // Walk up to the root: a/b < 1 is a left child of a/(b-a), a/b > 1 a
+        // right child of (a-b)/b.
+        let mut up = Vec::new();
+        while a != b {
+            if a < b {
+                up.push('L');
+                b = b.sub_unchecked_gte(&a);
+            } else {
+                up.push('R');
+                a = a.sub_unchecked_gte(&b);
+            }
+        }
+        Some(up.into_iter().rev().collect())
+    }
+
+    // Follow an L/R path down the Calkin–Wilf tree. None on any other
+    // character.
+    pub fn from_calkin_wilf_path(path: &str) -> Option<Rat<N>> {
+        let (mut a, mut b) = (N::one(), N::one());
+        for c in path.chars() {
+            match c {
+                'L' => b = a.add(&b),
+                'R' => a = a.add(&b),
+                _ => return None,
+            }
+        }
+        Some(positive(a, b))
+    }
+}
+
+// Z as a sequence: 0, 1, -1, 2, -2, ...; n = 2k - 1 gives k, n = 2k gives -k.
+pub fn nat_to_int<N: Natural>(n: &N) -> Int<N> {
+    let (k, odd) = n.div_mod(&N::from_usize(2)).expect("2 != 0");
+    if odd.is_zero() {
+        Int::from_nat(k).neg()
+    } else {
+        Int::from_nat(k.succ())
+    }
+}
+
+pub fn int_to_nat<N: Natural>(z: &Int<N>) -> N {
+    let twice = z.abs_nat().add(&z.abs_nat());
+    if z.is_negative() || z.is_zero() {
+        twice
+    } else {
+        twice.sub_unchecked_gte(&N::one())
+    }
+}
+
+// Q as a sequence: 0, then each Calkin–Wilf rational followed by its
+// negation. n = 2k - 1 gives the k-th Calkin–Wilf rational, n = 2k its
+// negation, and the k-th rational is found from the binary digits of k.
+pub fn nat_to_rat<N: Natural>(n: &N) -> Rat<N> {
+    let z = nat_to_int(n);
+    if z.is_zero() {
+        return Rat::zero();
+    }
+    // Below the leading 1, the bits of k spell the Calkin–Wilf path with
+    // 0 = L and 1 = R.
+    let two = N::from_usize(2);
+    let mut bits = Vec::new();
+    let mut k = z.abs_nat();
+    while k > N::one() {
+        let (q, r) = k.div_mod(&two).expect("2 != 0");
+        bits.push(if r.is_zero() { 'L' } else { 'R' });
+        k = q;
+    }
+    let path: String = bits.into_iter().rev().collect();
+    let x = Rat::from_calkin_wilf_path(&path).expect("path is L/R only");
+    if z.is_negative() { x.neg() } else { x }
+}
+
+pub fn rat_to_nat<N: Natural>(x: &Rat<N>) -> N {
+    if x.is_zero() {
+        return N::zero();
+    }
+    let path = x.abs().calkin_wilf_path().expect("|x| > 0");
+    let two = N::from_usize(2);
+    let k = path.chars().fold(N::one(), |k, c| {
+        let k = k.mul(&two);
+        if c == 'R' { k.succ() } else { k }
+    });
+    let k = Int::from_nat(k);
+    int_to_nat(&if x.is_negative() { k.neg() } else { k })
+}
+
+// a/b with a, b > 0 and coprime.
+fn positive<N: Natural>(a: N, b: N) -> Rat<N> {
+    Rat::new(Int::from_nat(a), b).expect("b != 0")
+}
+
+fn positive_parts<N: Natural>(x: &Rat<N>) -> Option<(N, N)> {
+    match &x.num {
+        Int::Pos(a) => Some((a.clone(), x.den.clone())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::{BinNat, Nat};
+
+    fn show<N: Natural>(xs: impl Iterator<Item = Rat<N>>) -> Vec<String> {
+        xs.map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn tree_orders() {
+        assert_eq!(
+            show(calkin_wilf::<Nat>().take(10)),
+            vec![
+                "1", "1/2", "2", "1/3", "3/2", "2/3", "3", "1/4", "4/3", "3/5"
+            ]
+        );
+        assert_eq!(
+            show(stern_brocot::<Nat>().take(7)),
+            vec!["1", "1/2", "2", "1/3", "2/3", "3/2", "3"]
+        );
+        // Each level of the Stern–Brocot tree is increasing.
+        let level: Vec<Rat<Nat>> = stern_brocot().skip(7).take(8).collect();
+        assert!(level.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn every_positive_rational_exactly_once() {
+        // The first 2^10 - 1 terms of each order are ten full tree levels:
+        // no repeats, and every a/b with a, b <= 5 is already there.
+        let n = (1 << 10) - 1;
+        for xs in [
+            calkin_wilf::<BinNat>().take(n).collect::<Vec<_>>(),
+            stern_brocot::<BinNat>().take(n).collect::<Vec<_>>(),
+        ] {
+            let mut sorted = xs.clone();
+            sorted.sort();
+            sorted.dedup();
+            assert_eq!(sorted.len(), n);
+            for a in 1..=5 {
+                for b in 1..=5 {
+                    let x = Rat::new(Int::from_nat(BinNat::from_usize(a)), BinNat::from_usize(b));
+                    assert!(xs.contains(&x.unwrap()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn paths_round_trip() {
+        let r = |s: &str| s.parse::<Rat<BinNat>>().unwrap();
+        assert_eq!(r("5/2").stern_brocot_path().as_deref(), Some("RRL"));
+        assert_eq!(r("5/2").calkin_wilf_path().as_deref(), Some("LRR"));
+        assert_eq!(r("1").stern_brocot_path().as_deref(), Some(""));
+        assert_eq!(r("0").stern_brocot_path(), None);
+        assert_eq!(r("-1/2").calkin_wilf_path(), None);
+        assert_eq!(Rat::<BinNat>::from_stern_brocot_path("RXL"), None);
+
+        for s in ["1", "5/2", "3/7", "355/113", "1/100"] {
+            let x = r(s);
+            let sb = x.stern_brocot_path().unwrap();
+            let cw = x.calkin_wilf_path().unwrap();
+            assert_eq!(sb.chars().rev().collect::<String>(), cw);
+            assert_eq!(Rat::from_stern_brocot_path(&sb), Some(x.clone()));
+            assert_eq!(Rat::from_calkin_wilf_path(&cw), Some(x));
+        }
+    }
+
+    #[test]
+    fn bijections_with_the_naturals() {
+        let ints: Vec<String> = (0..7)
+            .map(|n| nat_to_int(&Nat::from_usize(n)).to_string())
+            .collect();
+        assert_eq!(ints, vec!["0", "1", "-1", "2", "-2", "3", "-3"]);
+        let rats: Vec<String> = (0..7)
+            .map(|n| nat_to_rat(&BinNat::from_usize(n)).to_string())
+            .collect();
+        assert_eq!(rats, vec!["0", "1", "-1", "1/2", "-1/2", "2", "-2"]);
+
+        for n in 0..200 {
+            let n = BinNat::from_usize(n);
+            assert_eq!(int_to_nat(&nat_to_int(&n)), n);
+            assert_eq!(rat_to_nat(&nat_to_rat(&n)), n);
+        }
+        // The index of the k-th Calkin–Wilf term matches the iterator.
+        for (k, x) in calkin_wilf::<BinNat>().take(50).enumerate() {
+            assert_eq!(rat_to_nat(&x), BinNat::from_usize(2 * k + 1));
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use crate::n::Natural;
+use crate::z::{Int, Rounding};
+
+use super::Rat;
+
+// Farey sequences. F_n lists the reduced fractions in [0, 1] with
+// denominator at most n, in increasing order:
+//   F_5 = 0, 1/5, 1/4, 1/3, 2/5, 1/2, 3/5, 2/3, 3/4, 4/5, 1
+// Neighbours a/b < c/d in F_n satisfy bc - ad = 1, and the first fraction
+// to appear between them in a later F_m is their mediant (a+c)/(b+d).
+//
+// Ford circles draw this: the circle over p/q touching the x-axis with
+// radius 1/(2q^2). Two Ford circles never cross, and they are tangent
+// exactly when the fractions are Farey neighbours.
+
+impl<N: Natural> Rat<N> {
+    // (a+c)/(b+d) from the reduced forms a/b and c/d. It lies strictly
+    // between the two when they differ.
+    pub fn mediant(&self, other: &Rat<N>) -> Rat<N> {
+        Rat::new(self.num.add(&other.num), self.den.add(&other.den)).expect("den > 0")
+    }
+
+    // The fraction just below self in F_n. None if self is not in F_n (outside
+    // [0, 1] or denominator above n) or is 0.
+    pub fn farey_left(&self, n: &N) -> Option<Rat<N>> {
+        let (a, b) = farey_parts(self, n)?;
+        // The left neighbour p/q has aq - bp = 1, so q = a^-1 (mod b); take
+        // the largest such q <= n.
+        let q = largest_at_most(a.mod_inverse(&b)?, &b, n);
+        let aq = a.mul(&Int::from_nat(q.clone()));
+        if aq.is_zero() {
+            return None;
+        }
+        let p = aq.sub(&Int::from_nat(N::one())).abs_nat().div_exact(&b)?;
+        Rat::new(Int::from_nat(p), q)
+    }
+
+    // The fraction just above self in F_n. None if self is not in F_n or is 1.
+    pub fn farey_right(&self, n: &N) -> Option<Rat<N>> {
+        let (a, b) = farey_parts(self, n)?;
+        // The right neighbour r/s has br - as = 1, so s = -a^-1 (mod b).
+        let inv = a.mod_inverse(&b)?;
+        let s0 = Int::from_nat(inv)
+            .neg()
+            .rem_euclid(&Int::from_nat(b.clone()))?;
+        let s = largest_at_most(s0, &b, n);
+        let r = a
+            .mul(&Int::from_nat(s.clone()))
+            .abs_nat()
+            .succ()
+            .div_exact(&b)?;
+        if r > s {
+            return None;
+        }
+        Rat::new(Int::from_nat(r), s)
+    }
+
+    // The Ford circle over self.
+    pub fn ford_circle(&self) -> FordCircle<N> {
+        let q = Int::from_nat(self.den.clone());
+        let r = Rat::new(Int::from_nat(N::one()), N::from_usize(2))
+            .expect("2 != 0")
+            .div(&Rat::from_int(q.mul(&q)))
+            .expect("q != 0");
+        FordCircle {
+            fraction: self.clone(),
+            x: self.clone(),
+            y: r.clone(),
+            radius: r,
+        }
+    }
+}
+
+// F_n in increasing order. Empty for n = 0.
+pub fn farey<N: Natural>(n: &N) -> Vec<Rat<N>> {
+    if n.is_zero() {
+        return Vec::new();
+    }
+    // Walk neighbour to neighbour: after a/b < c/d comes
+    // (kc - a)/(kd - b) with k = floor((n + b) / d).
+    let (mut a, mut b) = (N::zero(), N::one());
+    let (mut c, mut d) = (N::one(), n.clone());
+    let mut out = vec![Rat::zero()];
+    while c <= *n {
+        out.push(Rat::new(Int::from_nat(c.clone()), d.clone()).expect("d > 0"));
+        let (k, _) = n.add(&b).div_mod(&d).expect("d > 0");
+        let e = k.mul(&c).sub_unchecked_gte(&a);
+        let f = k.mul(&d).sub_unchecked_gte(&b);
+        (a, b, c, d) = (c, d, e, f);
+    }
+    out
+}
+
+// A circle in the upper half plane, with exact coordinates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FordCircle<N> {
+    pub fraction: Rat<N>,
+    pub x: Rat<N>,
+    pub y: Rat<N>,
+    pub radius: Rat<N>,
+}
+
+// The Ford circles of F_n as CSV for plotting: the exact fraction, then the
+// center and radius rounded to `digits` decimal places. (Rounding scales by
+// 10^digits, so use a binary backend for more than a few digits.)
+pub fn ford_circles_csv<N: Natural>(n: &N, digits: usize) -> String {
+    let mut out = String::from("fraction,x,y,radius\n");
+    for c in farey(n).iter().map(Rat::ford_circle) {
+        let fixed = |v: &Rat<N>| v.to_fixed_string(digits, Rounding::HalfEven);
+        out.push_str(&format!(
+            "{},{},{},{}\n",
+            c.fraction,
+            fixed(&c.x),
+            fixed(&c.y),
+            fixed(&c.radius)
+        ));
+    }
+    out
+}
+
+// (a, b) for self = a/b in F_n.
+fn farey_parts<N: Natural>(x: &Rat<N>, n: &N) -> Option<(Int<N>, N)> {
+    if x.is_negative() || x.den > *n || x.num.abs_nat() > x.den {
+        return None;
+    }
+    Some((x.num.clone(), x.den.clone()))
+}
+
+// The largest v <= n with v = r (mod b), given r < b <= n.
+fn largest_at_most<N: Natural>(r: N, b: &N, n: &N) -> N {
+    let (k, _) = n.sub_unchecked_gte(&r).div_mod(b).expect("b != 0");
+    r.add(&k.mul(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::{BinNat, Nat};
+    use crate::nt::totient;
+    use crate::q::test_util::rat;
+
+    #[test]
+    fn farey_sequences() {
+        let f5: Vec<String> = farey(&Nat::from_usize(5))
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(
+            f5,
+            vec![
+                "0", "1/5", "1/4", "1/3", "2/5", "1/2", "3/5", "2/3", "3/4", "4/5", "1"
+            ]
+        );
+        assert!(farey(&Nat::zero()).is_empty());
+        assert_eq!(farey(&Nat::one()), vec![rat(0, 1), rat(1, 1)]);
+
+        for n in 1..=12 {
+            let f = farey(&Nat::from_usize(n));
+            // |F_n| = 1 + phi(1) + ... + phi(n).
+            let len = (1..=n).fold(1, |acc, k| {
+                acc + totient(&Nat::from_usize(k)).unwrap().to_usize()
+            });
+            assert_eq!(f.len(), len);
+            for w in f.windows(2) {
+                // bc - ad = 1, and the mediant sits strictly between.
+                let (ad, bc) = (
+                    w[0].num.mul(&Int::from_nat(w[1].den.clone())),
+                    w[1].num.mul(&Int::from_nat(w[0].den.clone())),
+                );
+                assert_eq!(bc.sub(&ad), Int::from_nat(Nat::one()));
+                let m = w[0].mediant(&w[1]);
+                assert!(w[0] < m && m < w[1]);
+            }
+        }
+    }
+
+    #[test]
+    fn neighbours() {
+        let n = Nat::from_usize;
+        assert_eq!(rat(2, 5).farey_left(&n(5)), Some(rat(1, 3)));
+        assert_eq!(rat(2, 5).farey_right(&n(5)), Some(rat(1, 2)));
+        assert_eq!(rat(0, 1).farey_left(&n(5)), None);
+        assert_eq!(rat(0, 1).farey_right(&n(5)), Some(rat(1, 5)));
+        assert_eq!(rat(1, 1).farey_left(&n(5)), Some(rat(4, 5)));
+        assert_eq!(rat(1, 1).farey_right(&n(5)), None);
+        assert_eq!(rat(1, 7).farey_right(&n(5)), None); // not in F_5
+        assert_eq!(rat(3, 2).farey_left(&n(5)), None);
+
+        // Agrees with the sequence itself.
+        let f = farey(&n(9));
+        for w in f.windows(2) {
+            assert_eq!(w[0].farey_right(&n(9)).as_ref(), Some(&w[1]));
+            assert_eq!(w[1].farey_left(&n(9)).as_ref(), Some(&w[0]));
+        }
+    }
+
+    #[test]
+    fn ford_circles_of_neighbours_are_tangent() {
+        // Squared distances get large denominators; use the binary backend.
+        let f = farey(&BinNat::from_usize(8));
+        for w in f.windows(2) {
+            let (c1, c2) = (w[0].ford_circle(), w[1].ford_circle());
+            let dx = c1.x.sub(&c2.x);
+            let dy = c1.y.sub(&c2.y);
+            let rsum = c1.radius.add(&c2.radius);
+            assert_eq!(dx.mul(&dx).add(&dy.mul(&dy)), rsum.mul(&rsum));
+        }
+        assert_eq!(rat(1, 2).ford_circle().radius, rat(1, 8));
+
+        let csv = ford_circles_csv(&BinNat::from_usize(3), 6);
+        assert_eq!(
+            csv,
+            "fraction,x,y,radius\n\
+             0,0.000000,0.500000,0.500000\n\
+             1/3,0.333333,0.055556,0.055556\n\
+             1/2,0.500000,0.125000,0.125000\n\
+             2/3,0.666667,0.055556,0.055556\n\
+             1,1.000000,0.500000,0.500000\n"
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::n::{Natural, ParseNatError, ParseNatErrorKind};
+use crate::z::Int;
+
+use super::Rat;
+
+// Parsing rationals. Accepted forms, each with an optional leading sign:
+// - Fraction:   -3/4
+// - Mixed:      2 1/3        (= 7/3; the sign applies to the whole number)
+// - Decimal:    0.125
+// - Scientific: 1.5e-3       (also 2E4, 1.25e+2)
+// - Repeating:  0.(3), 0.1(6), 1.(142857)e1
+// Everything is exact: each form is assembled from naturals and `Rat::new`.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseRatErrorKind {
+    Empty,
+    InvalidDigit(char),
+    // The given text was expected at this position.
+    Expected(&'static str),
+    TrailingInput,
+    ZeroDenominator,
+}
+
+// A parse failure and the byte offset (into the trimmed input) where it happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseRatError {
+    kind: ParseRatErrorKind,
+    pos: usize,
+}
+
+impl ParseRatError {
+    fn new(kind: ParseRatErrorKind, pos: usize) -> ParseRatError {
+        ParseRatError { kind, pos }
+    }
+
+    pub fn kind(&self) -> &ParseRatErrorKind {
+        &self.kind
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl From<ParseNatError> for ParseRatError {
+    fn from(e: ParseNatError) -> ParseRatError {
+        let kind = match e.kind() {
+            ParseNatErrorKind::Empty => ParseRatErrorKind::Empty,
+            ParseNatErrorKind::InvalidDigit(c) => ParseRatErrorKind::InvalidDigit(*c),
+            ParseNatErrorKind::Expected(what) => ParseRatErrorKind::Expected(what),
+            ParseNatErrorKind::TrailingInput => ParseRatErrorKind::TrailingInput,
+        };
+        ParseRatError::new(kind, e.position())
+    }
+}
+
+impl fmt::Display for ParseRatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseRatErrorKind::Empty => write!(f, "cannot parse a rational from empty input"),
+            ParseRatErrorKind::InvalidDigit(c) => {
+                write!(f, "invalid digit {:?} at position {}", c, self.pos)
+            }
+            ParseRatErrorKind::Expected(what) => {
+                write!(f, "expected {:?} at position {}", what, self.pos)
+            }
+            ParseRatErrorKind::TrailingInput => {
+                write!(f, "unexpected trailing input at position {}", self.pos)
+            }
+            ParseRatErrorKind::ZeroDenominator => {
+                write!(f, "zero denominator at position {}", self.pos)
+            }
+        }
+    }
+}
+
+impl Error for ParseRatError {}
+
+impl<N: Natural> FromStr for Rat<N> {
+    type Err = ParseRatError;
+
+    fn from_str(s: &str) -> Result<Rat<N>, ParseRatError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseRatError::new(ParseRatErrorKind::Empty, 0));
+        }
+        let (negative, body) = match s.as_bytes()[0] {
+            b'-' => (true, &s[1..]),
+            b'+' => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let off = s.len() - body.len();
+
+        let x = if let Some(sp) = body.find(char::is_whitespace) {
+            // Mixed number: whole part, whitespace, fraction.
+            let whole: N = digits(&body[..sp], off)?;
+            let rest = body[sp..].trim_start();
+            let frac = parse_fraction(rest, off + body.len() - rest.len())?;
+            Rat::from_int(Int::from_nat(whole)).add(&frac)
+        } else if body.contains('/') {
+            parse_fraction(body, off)?
+        } else {
+            parse_decimal(body, off)?
+        };
+        Ok(if negative { x.neg() } else { x })
+    }
+}
+
+// Unsigned num/den.
+fn parse_fraction<N: Natural>(s: &str, off: usize) -> Result<Rat<N>, ParseRatError> {
+    let slash = s
+        .find('/')
+        .ok_or_else(|| ParseRatError::new(ParseRatErrorKind::Expected("/"), off + s.len()))?;
+    let num: N = digits(&s[..slash], off)?;
+    let den: N = digits(&s[slash + 1..], off + slash + 1)?;
+    Rat::new(Int::from_nat(num), den)
+        .ok_or_else(|| ParseRatError::new(ParseRatErrorKind::ZeroDenominator, off + slash + 1))
+}
+
+// Unsigned int[.pre[(period)]][e[+-]exp].
+fn parse_decimal<N: Natural>(s: &str, off: usize) -> Result<Rat<N>, ParseRatError> {
+    let ten = N::from_usize(10);
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], Some((&s[i + 1..], off + i + 1))),
+        None => (s, None),
+    };
+    let (int_str, frac) = match mantissa.find('.') {
+        Some(i) => (&mantissa[..i], Some((&mantissa[i + 1..], off + i + 1))),
+        None => (mantissa, None),
+    };
+    let int_part: N = digits(int_str, off)?;
+    let mut x = Rat::from_int(Int::from_nat(int_part));
+
+    if let Some((frac, frac_off)) = frac {
+        // pre(period): x += pre / 10^p + period / (10^p (10^r - 1)).
+        let (pre, period) = match frac.find('(') {
+            Some(i) => {
+                let close = frac.find(')').ok_or_else(|| {
+                    ParseRatError::new(ParseRatErrorKind::Expected(")"), frac_off + frac.len())
+                })?;
+                if close + 1 != frac.len() {
+                    return Err(ParseRatError::new(
+                        ParseRatErrorKind::TrailingInput,
+                        frac_off + close + 1,
+                    ));
+                }
+                (&frac[..i], Some((&frac[i + 1..close], frac_off + i + 1)))
+            }
+            None => (frac, None),
+        };
+        if pre.is_empty() && period.is_none() {
+            return Err(ParseRatError::new(
+                ParseRatErrorKind::Expected("digits"),
+                frac_off,
+            ));
+        }
+        let scale = ten.pow(&N::from_usize(pre.len()));
+        if !pre.is_empty() {
+            let p: N = digits(pre, frac_off)?;
+            x = x.add(&Rat::new(Int::from_nat(p), scale.clone()).expect("10^p != 0"));
+        }
+        if let Some((period, period_off)) = period {
+            let r: N = digits(period, period_off)?;
+            let nines = ten
+                .pow(&N::from_usize(period.len()))
+                .sub_unchecked_gte(&N::one());
+            x = x.add(&Rat::new(Int::from_nat(r), scale.mul(&nines)).expect("nines != 0"));
+        }
+    }
+
+    if let Some((exp, exp_off)) = exponent {
+        let (negative, digits_str) = match exp.as_bytes().first() {
+            Some(b'-') => (true, &exp[1..]),
+            Some(b'+') => (false, &exp[1..]),
+            _ => (false, exp),
+        };
+        let e: N = digits(digits_str, exp_off + exp.len() - digits_str.len())?;
+        let p = Rat::from_int(Int::from_nat(ten.pow(&e)));
+        x = if negative {
+            x.div(&p).expect("10^e != 0")
+        } else {
+            x.mul(&p)
+        };
+    }
+    Ok(x)
+}
+
+fn digits<N: Natural>(s: &str, off: usize) -> Result<N, ParseRatError> {
+    Ok(crate::n::parse_digits(s, 10, off)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::BinNat;
+    use crate::q::test_util::rat;
+
+    #[test]
+    fn parses_every_form() {
+        let p = |s: &str| s.parse::<Rat>();
+        assert_eq!(p("-3/4"), Ok(rat(-3, 4)));
+        assert_eq!(p(" 6/8 "), Ok(rat(3, 4)));
+        assert_eq!(p("+5"), Ok(rat(5, 1)));
+        assert_eq!(p("2 1/3"), Ok(rat(7, 3)));
+        assert_eq!(p("-2 1/3"), Ok(rat(-7, 3)));
+        assert_eq!(p("0.125"), Ok(rat(1, 8)));
+        assert_eq!(p("-1.50"), Ok(rat(-3, 2)));
+        assert_eq!(p("1.5e-3"), Ok(rat(3, 2000)));
+        assert_eq!(p("2E2"), Ok(rat(200, 1)));
+        assert_eq!(p("1.25e+1"), Ok(rat(25, 2)));
+        assert_eq!(p("0.(3)"), Ok(rat(1, 3)));
+        assert_eq!(p("0.1(6)"), Ok(rat(1, 6)));
+        assert_eq!(p("-0.(9)"), Ok(rat(-1, 1)));
+        // Long periods make big numbers; use the binary backend.
+        assert_eq!(
+            "0.(142857)e1".parse::<Rat<BinNat>>().map(|x| x.to_string()),
+            Ok("10/7".to_string())
+        );
+    }
+
+    #[test]
+    fn round_trips_decimal_strings() {
+        for s in ["1/7", "1/6", "-22/7", "5/4", "0", "1/12"] {
+            let x: Rat<BinNat> = s.parse().unwrap();
+            assert_eq!(x.to_decimal_string().parse(), Ok(x.clone()));
+            assert_eq!(x.to_string(), s);
+        }
+        let big = "123456789012345678901234567890.(0123)"
+            .parse::<Rat<BinNat>>()
+            .unwrap();
+        assert_eq!(
+            big.to_decimal_string(),
+            "123456789012345678901234567890.(0123)"
+        );
+    }
+
+    #[test]
+    fn reports_errors_with_positions() {
+        let err = |s: &str| s.parse::<Rat>().unwrap_err();
+        assert_eq!(err("").kind(), &ParseRatErrorKind::Empty);
+        assert_eq!(err("3/0").kind(), &ParseRatErrorKind::ZeroDenominator);
+        assert_eq!(err("3/0").position(), 2);
+        assert_eq!(err("2 1/0").position(), 4);
+        assert_eq!(err("1.2x").kind(), &ParseRatErrorKind::InvalidDigit('x'));
+        assert_eq!(err("1.2x").position(), 3);
+        assert_eq!(err("-3/").kind(), &ParseRatErrorKind::Expected("digits"));
+        assert_eq!(err("-3/").position(), 3);
+        assert_eq!(err("1.").kind(), &ParseRatErrorKind::Expected("digits"));
+        assert_eq!(err("0.(3").kind(), &ParseRatErrorKind::Expected(")"));
+        assert_eq!(err("0.(3)4").kind(), &ParseRatErrorKind::TrailingInput);
+        assert_eq!(err("0.(3)4").position(), 5);
+        assert_eq!(err("0.()").kind(), &ParseRatErrorKind::Expected("digits"));
+        assert_eq!(err("1e").position(), 2);
+        assert_eq!(err("2 1").kind(), &ParseRatErrorKind::Expected("/"));
+        assert_eq!(err("3/0").to_string(), "zero denominator at position 2");
+    }
+}
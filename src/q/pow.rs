@@ -0,0 +1,76 @@
+use crate::n::Natural;
+use crate::z::Int;
+
+use super::Rat;
+
+// Powers and exact roots. Since self is kept in lowest terms and
+// gcd(a, b) = 1 implies gcd(a^n, b^n) = 1, both work on the numerator and
+// the denominator separately.
+impl<N: Natural> Rat<N> {
+    // self^exp for any integer exponent; x^0 = 1 (including 0^0). None for
+    // 0 to a negative power.
+    pub fn pow(&self, exp: &Int<N>) -> Option<Rat<N>> {
+        let e = exp.abs_nat();
+        let p = Rat::new(self.num.pow(&e), self.den.pow(&e)).expect("den^e != 0");
+        if exp.is_negative() {
+            Rat::one().div(&p)
+        } else {
+            Some(p)
+        }
+    }
+
+    // The rational r with r^n = self, if there is one: sqrt(9/4) = 3/2,
+    // cbrt(-8/27) = -2/3. None if n == 0, if n is even and self < 0, or if
+    // numerator or denominator is not a perfect n-th power (the root is
+    // irrational then).
+    pub fn checked_root(&self, n: &N) -> Option<Rat<N>> {
+        let a = self.num.nth_root(n)?;
+        let b = self.den.nth_root(n)?;
+        if a.pow(n) != self.num || b.pow(n) != self.den {
+            return None;
+        }
+        Rat::new(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::{BinNat, Nat};
+    use crate::q::test_util::{int, rat};
+
+    #[test]
+    fn integer_powers() {
+        assert_eq!(rat(2, 3).pow(&int(3)), Some(rat(8, 27)));
+        assert_eq!(rat(2, 3).pow(&int(-2)), Some(rat(9, 4)));
+        assert_eq!(rat(-2, 3).pow(&int(-3)), Some(rat(-27, 8)));
+        assert_eq!(rat(-2, 3).pow(&int(0)), Some(rat(1, 1)));
+        assert_eq!(rat(0, 1).pow(&int(0)), Some(rat(1, 1)));
+        assert_eq!(rat(0, 1).pow(&int(2)), Some(rat(0, 1)));
+        assert_eq!(rat(0, 1).pow(&int(-1)), None);
+        // x^a * x^b = x^(a+b).
+        let x = rat(-3, 2);
+        for (a, b) in [(2, -5), (-1, -1), (3, 0)] {
+            let lhs = x.pow(&int(a)).unwrap().mul(&x.pow(&int(b)).unwrap());
+            assert_eq!(Some(lhs), x.pow(&int(a + b)));
+        }
+    }
+
+    #[test]
+    fn exact_roots() {
+        let n = Nat::from_usize;
+        assert_eq!(rat(9, 4).checked_root(&n(2)), Some(rat(3, 2)));
+        assert_eq!(rat(-8, 27).checked_root(&n(3)), Some(rat(-2, 3)));
+        assert_eq!(rat(5, 7).checked_root(&n(1)), Some(rat(5, 7)));
+        assert_eq!(rat(0, 1).checked_root(&n(4)), Some(rat(0, 1)));
+        assert_eq!(rat(2, 1).checked_root(&n(2)), None); // irrational
+        assert_eq!(rat(4, 3).checked_root(&n(2)), None);
+        assert_eq!(rat(-4, 9).checked_root(&n(2)), None); // even root of a negative
+        assert_eq!(rat(4, 9).checked_root(&n(0)), None);
+
+        let b = |s: &str| s.parse::<Rat<BinNat>>().unwrap();
+        let x = b("-123456789/1000");
+        let cube = x.pow(&Int::from_nat(BinNat::from_usize(3))).unwrap();
+        assert_eq!(cube.checked_root(&BinNat::from_usize(3)), Some(x));
+    }
+}
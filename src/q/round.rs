@@ -0,0 +1,105 @@
+use crate::n::Natural;
+use crate::z::{Int, Rounding};
+
+use super::Rat;
+
+// Back from Q to Z. Every rounding is the quotient num / den under one of
+// the `Rounding` conventions from `Int::div_rem`, so all of them agree with
+// integer division:
+//
+//                     -1.5   -1.25   1.25   1.5   2.5
+//   floor              -2     -2      1      1     2
+//   ceil               -1     -1      2      2     3
+//   trunc              -1     -1      1      1     2
+//   HalfEven           -2     -1      1      2     2
+//   HalfAwayFromZero   -2     -1      1      2     3
+//   HalfUp             -1     -1      1      2     3
+impl<N: Natural> Rat<N> {
+    // The integer self rounds to under `rounding`. (`Rounding::Euclid` is
+    // `Floor` here, since the denominator is positive.)
+    pub fn round(&self, rounding: Rounding) -> Int<N> {
+        let den = Int::from_nat(self.den.clone());
+        self.num.div_rem(&den, rounding).expect("den != 0").0
+    }
+
+    // The largest integer <= self.
+    pub fn floor(&self) -> Int<N> {
+        self.round(Rounding::Floor)
+    }
+
+    // The smallest integer >= self.
+    pub fn ceil(&self) -> Int<N> {
+        self.round(Rounding::Ceil)
+    }
+
+    // The integer part, rounding toward zero.
+    pub fn trunc(&self) -> Int<N> {
+        self.round(Rounding::Trunc)
+    }
+
+    // self - floor(self), always in [0, 1): fract(-1.25) = 0.75. (Rust's
+    // f64::fract uses trunc instead and gives -0.25.)
+    pub fn fract(&self) -> Rat<N> {
+        self.sub(&Rat::from_int(self.floor()))
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == N::one()
+    }
+
+    // self as an Int, or None if it has a fractional part.
+    pub fn to_int_exact(&self) -> Option<Int<N>> {
+        if self.is_integer() {
+            Some(self.num.clone())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::q::test_util::{int, rat};
+
+    #[test]
+    fn rounding_table() {
+        // The table at the top of this file.
+        let xs = [rat(-3, 2), rat(-5, 4), rat(5, 4), rat(3, 2), rat(5, 2)];
+        let rows = [
+            (Rounding::Floor, [-2, -2, 1, 1, 2]),
+            (Rounding::Ceil, [-1, -1, 2, 2, 3]),
+            (Rounding::Trunc, [-1, -1, 1, 1, 2]),
+            (Rounding::HalfEven, [-2, -1, 1, 2, 2]),
+            (Rounding::HalfAwayFromZero, [-2, -1, 1, 2, 3]),
+            (Rounding::HalfUp, [-1, -1, 1, 2, 3]),
+        ];
+        for (rounding, expected) in rows {
+            for (x, e) in xs.iter().zip(expected) {
+                assert_eq!(x.round(rounding), int(e), "{:?} at {}", rounding, x);
+            }
+        }
+        for x in &xs {
+            assert_eq!(x.floor(), x.round(Rounding::Floor));
+            assert_eq!(x.ceil(), x.round(Rounding::Ceil));
+            assert_eq!(x.trunc(), x.round(Rounding::Trunc));
+        }
+        // Integers round to themselves in every mode.
+        assert_eq!(rat(-4, 1).round(Rounding::HalfEven), int(-4));
+        assert_eq!(rat(7, 1).ceil(), int(7));
+    }
+
+    #[test]
+    fn fract_and_exact_conversion() {
+        assert_eq!(rat(-5, 4).fract(), rat(3, 4));
+        assert_eq!(rat(7, 3).fract(), rat(1, 3));
+        assert_eq!(rat(2, 1).fract(), rat(0, 1));
+        for x in [rat(-5, 4), rat(7, 3), rat(-1, 2)] {
+            assert_eq!(Rat::from_int(x.floor()).add(&x.fract()), x);
+        }
+        assert_eq!(rat(6, 3).to_int_exact(), Some(int(2)));
+        assert_eq!(rat(-6, 1).to_int_exact(), Some(int(-6)));
+        assert_eq!(rat(1, 2).to_int_exact(), None);
+        assert!(!rat(1, 2).is_integer());
+    }
+}
@@ -1,31 +1,38 @@
 use std::cmp::Ordering;
 use std::fmt;
-use std::sync::Arc;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::{Arc, Mutex};
 
-use crate::n::Nat;
+use crate::n::{Nat, Natural};
 use crate::q::Rat;
 use crate::z::Int;
 
+mod consts;
+mod exp;
+mod hyperbolic;
+mod root;
+mod trig;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Interval {
-    pub lo: Rat,
-    pub hi: Rat,
+pub struct Interval<N = Nat> {
+    pub lo: Rat<N>,
+    pub hi: Rat<N>,
 }
 
-impl Interval {
-    pub fn new(lo: Rat, hi: Rat) -> Interval {
+impl<N: Natural> Interval<N> {
+    pub fn new(lo: Rat<N>, hi: Rat<N>) -> Interval<N> {
         assert!(lo <= hi, "invalid interval: lo > hi");
         Interval { lo, hi }
     }
 
-    pub fn point(x: Rat) -> Interval {
+    pub fn point(x: Rat<N>) -> Interval<N> {
         Interval {
             lo: x.clone(),
             hi: x,
         }
     }
 
-    pub fn width(&self) -> Rat {
+    pub fn width(&self) -> Rat<N> {
         self.hi.sub(&self.lo)
     }
 
@@ -37,104 +44,175 @@ impl Interval {
 
 // Computable/Cauchy-style reals via interval approximation.
 //
-// Contract: `interval(k)` returns [lo, hi] with:
+// Contract: `approx(b)` returns [lo, hi] with:
 // - lo <= x <= hi
-// - width(hi - lo) <= 1/k   (k >= 1)
+// - width(hi - lo) <= 2^-b
+// - lo and hi dyadic (denominator a power of two), except that an exact
+//   rational point stays exact, so rational arithmetic is not rounded.
+//
+// Every operation asks its inputs for a few more bits than it needs, then
+// rounds its result outward onto the grid 2^-(b+2). Denominators therefore
+// stay at about b bits however deep an expression is, where exact endpoints
+// would multiply them at every step.
 //
-// We use 1/k (not 2^-k) to keep the Peano denominators manageable.
+// `interval(k)` (width <= 1/k) is the original interface and keeps working:
+// it is `approx(b)` for the smallest b with 2^b >= k.
 #[derive(Clone)]
-pub struct Real {
-    interval_fn: Arc<dyn Fn(usize) -> Interval + Send + Sync>,
+pub struct Real<N = Nat> {
+    approx_fn: Arc<dyn Fn(usize) -> Interval<N> + Send + Sync>,
+    // The most precise enclosure so far, which also answers every request
+    // for fewer bits: shared subexpressions (x in x(1 - x)) and the operand
+    // bounds in `mul` are not recomputed.
+    cache: Arc<Cache<N>>,
 }
 
-impl Real {
-    pub fn from_rat(r: Rat) -> Real {
+type Cache<N> = Mutex<Option<(usize, Interval<N>)>>;
+
+impl<N: Natural> Real<N> {
+    pub fn from_rat(r: Rat<N>) -> Real<N> {
+        Real::from_fn(move |_b| Interval::point(r.clone()))
+    }
+
+    // A Real from an enclosure function; `f` must keep the contract above.
+    fn from_fn(f: impl Fn(usize) -> Interval<N> + Send + Sync + 'static) -> Real<N> {
         Real {
-            interval_fn: Arc::new(move |_k| Interval::point(r.clone())),
+            approx_fn: Arc::new(f),
+            cache: Arc::new(Mutex::new(None)),
         }
     }
 
-    pub fn interval(&self, k: usize) -> Interval {
-        assert!(k >= 1, "k must be >= 1");
-        (self.interval_fn)(k)
+    // A Real from an interval extension of some f: `f(i, q)` must enclose
+    // f(x) for every x in i and tighten as q grows, or return None to ask
+    // for a finer i. Here i = self.approx(q); the extra bits in q double
+    // until the result has width <= 2^-(b+1), which is then rounded outward.
+    // The extra bits that sufficed are the first guess next time.
+    fn refine_map(
+        &self,
+        f: impl Fn(&Interval<N>, usize) -> Option<Interval<N>> + Send + Sync + 'static,
+    ) -> Real<N> {
+        let x = self.clone();
+        let hint = AtomicUsize::new(2);
+        Real::from_fn(move |b| {
+            let mut extra = hint.load(atomic::Ordering::Relaxed);
+            loop {
+                let q = b + extra;
+                if let Some(out) = f(&x.approx(q), q)
+                    && fits(&out, b + 1)
+                {
+                    hint.store(extra, atomic::Ordering::Relaxed);
+                    return outward(out, b);
+                }
+                extra *= 2;
+            }
+        })
     }
 
-    pub fn add(&self, other: &Real) -> Real {
-        let a = self.clone();
-        let b = other.clone();
-        Real {
-            interval_fn: Arc::new(move |k| {
-                // If each width <= 1/(2k), then sum width <= 1/k.
-                let kk = k.saturating_mul(2).max(1);
-                let ia = a.interval(kk);
-                let ib = b.interval(kk);
-                Interval::new(ia.lo.add(&ib.lo), ia.hi.add(&ib.hi))
-            }),
-        }
+    // max(self, c) and min(self, c) for a rational c. Both are 1-Lipschitz,
+    // so they never widen an enclosure.
+    fn max_rat(&self, c: &Rat<N>) -> Real<N> {
+        let (x, c) = (self.clone(), c.clone());
+        Real::from_fn(move |b| {
+            let i = x.approx(b);
+            Interval::new(i.lo.max(c.clone()), i.hi.max(c.clone()))
+        })
     }
 
-    pub fn sub(&self, other: &Real) -> Real {
-        let a = self.clone();
-        let b = other.clone();
-        Real {
-            interval_fn: Arc::new(move |k| {
-                let kk = k.saturating_mul(2).max(1);
-                let ia = a.interval(kk);
-                let ib = b.interval(kk);
-                Interval::new(ia.lo.sub(&ib.hi), ia.hi.sub(&ib.lo))
-            }),
-        }
+    fn min_rat(&self, c: &Rat<N>) -> Real<N> {
+        let (x, c) = (self.clone(), c.clone());
+        Real::from_fn(move |b| {
+            let i = x.approx(b);
+            Interval::new(i.lo.min(c.clone()), i.hi.min(c.clone()))
+        })
     }
 
-    pub fn mul(&self, other: &Real) -> Real {
-        let a = self.clone();
-        let b = other.clone();
-        Real {
-            interval_fn: Arc::new(move |k| {
-                // Refine operand intervals until product interval is narrow enough.
-                let target = Rat::new(Int::from_nat(Nat::one()), Nat::from_usize(k)).unwrap(); // 1/k
-                let mut p = k.max(1);
-                loop {
-                    let ia = a.interval(p);
-                    let ib = b.interval(p);
-                    let prod = interval_mul(&ia, &ib);
-                    if prod.width() <= target {
-                        return prod;
-                    }
-                    p = p.saturating_mul(2);
-                }
-            }),
+    // An enclosure of width at most 2^-bits.
+    pub fn approx(&self, bits: usize) -> Interval<N> {
+        if let Some((b, i)) = &*self.cache.lock().expect("cache lock")
+            && *b >= bits
+        {
+            return i.clone();
         }
+        let i = (self.approx_fn)(bits);
+        let mut cache = self.cache.lock().expect("cache lock");
+        if cache.as_ref().is_none_or(|(b, _)| *b < bits) {
+            *cache = Some((bits, i.clone()));
+        }
+        i
     }
 
-    pub fn div(&self, other: &Real) -> Option<Real> {
-        let a = self.clone();
-        let b = other.clone();
-        Some(Real {
-            interval_fn: Arc::new(move |k| {
-                let target = Rat::new(Int::from_nat(Nat::one()), Nat::from_usize(k)).unwrap(); // 1/k
-                let mut p = k.max(1);
-                loop {
-                    let ia = a.interval(p);
-                    let ib = b.interval(p);
-                    if ib.contains_zero() {
-                        // For a nonzero real, higher precision will eventually separate it from 0.
-                        p = p.saturating_mul(2);
-                        continue;
-                    }
-                    let inv = interval_inv(&ib);
-                    let q = interval_mul(&ia, &inv);
-                    if q.width() <= target {
-                        return q;
-                    }
-                    p = p.saturating_mul(2);
+    // An enclosure of width at most 1/k.
+    pub fn interval(&self, k: usize) -> Interval<N> {
+        assert!(k >= 1, "k must be >= 1");
+        self.approx(bits_for(k))
+    }
+
+    pub fn add(&self, other: &Real<N>) -> Real<N> {
+        let (x, y) = (self.clone(), other.clone());
+        Real::from_fn(move |b| {
+            // Widths 2^-(b+2) each, plus at most 2^-(b+1) from rounding.
+            let (i, j) = (x.approx(b + 2), y.approx(b + 2));
+            outward(Interval::new(i.lo.add(&j.lo), i.hi.add(&j.hi)), b)
+        })
+    }
+
+    pub fn sub(&self, other: &Real<N>) -> Real<N> {
+        let (x, y) = (self.clone(), other.clone());
+        Real::from_fn(move |b| {
+            let (i, j) = (x.approx(b + 2), y.approx(b + 2));
+            outward(Interval::new(i.lo.sub(&j.hi), i.hi.sub(&j.lo)), b)
+        })
+    }
+
+    pub fn neg(&self) -> Real<N> {
+        let x = self.clone();
+        Real::from_fn(move |b| {
+            let i = x.approx(b);
+            Interval::new(i.hi.neg(), i.lo.neg())
+        })
+    }
+
+    pub fn mul(&self, other: &Real<N>) -> Real<N> {
+        let (x, y) = (self.clone(), other.clone());
+        let hint = AtomicUsize::new(8);
+        Real::from_fn(move |b| {
+            // With widths w <= 2^-q, the product is at most
+            // (|x| + |y| + 1) 2^-q wide. The first guess covers |x| + |y|
+            // up to about 2^6; a miss bounds |x| and |y| from the operands
+            // just computed, so the second round fits. Probing |x| and |y|
+            // at low precision first would instead send a separate request
+            // down the whole expression for every product in it.
+            let mut extra = hint.load(atomic::Ordering::Relaxed);
+            loop {
+                let q = b + extra;
+                let (i, j) = (x.approx(q), y.approx(q));
+                let prod = interval_mul(&i, &j);
+                if fits(&prod, b + 1) {
+                    hint.store(extra, atomic::Ordering::Relaxed);
+                    return outward(prod, b);
                 }
-            }),
+                let bound = magnitude(&i).add(&magnitude(&j)).add(&N::from_usize(3));
+                extra = (2 * extra).max(1 + bits_of(&bound));
+            }
+        })
+    }
+
+    pub fn div(&self, other: &Real<N>) -> Option<Real<N>> {
+        Some(self.mul(&other.recip()))
+    }
+
+    // 1/self. For a nonzero real, more bits eventually separate it from 0;
+    // for 0 this refines forever.
+    fn recip(&self) -> Real<N> {
+        self.refine_map(|i, _| {
+            if i.contains_zero() {
+                return None;
+            }
+            Some(interval_inv(i))
         })
     }
 }
 
-impl fmt::Debug for Real {
+impl<N: Natural> fmt::Debug for Real<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Debug shows a moderately precise interval.
         let i = self.interval(16);
@@ -142,7 +220,7 @@ impl fmt::Debug for Real {
     }
 }
 
-impl fmt::Display for Real {
+impl<N: Natural> fmt::Display for Real<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Display a small interval to show the value is approximated.
         let i = self.interval(16);
@@ -154,7 +232,78 @@ impl fmt::Display for Real {
     }
 }
 
-fn interval_mul(a: &Interval, b: &Interval) -> Interval {
+// Operators. `/` never fails up front: like `div`, a zero divisor only shows
+// up as `interval` refining forever.
+forward_binop!([N: Natural] Real<N>, Add::add, AddAssign::add_assign, |a, b| Real::add(a, b));
+forward_binop!([N: Natural] Real<N>, Sub::sub, SubAssign::sub_assign, |a, b| Real::sub(a, b));
+forward_binop!([N: Natural] Real<N>, Mul::mul, MulAssign::mul_assign, |a, b| Real::mul(a, b));
+forward_binop!([N: Natural] Real<N>, Div::div, DivAssign::div_assign, |a, b| {
+    Real::div(a, b).expect("Real::div always returns Some")
+});
+forward_neg!([N: Natural] Real<N>, |a| Real::neg(a));
+forward_sum_product!(
+    [N: Natural] Real<N>,
+    zero = Real::from_rat(Rat::zero()),
+    one = Real::from_rat(Rat::one())
+);
+
+// 2^bits and 2^-bits.
+fn pow2<N: Natural>(bits: usize) -> N {
+    N::from_usize(2).pow(&N::from_usize(bits))
+}
+
+fn pow2_recip<N: Natural>(bits: usize) -> Rat<N> {
+    Rat::new(Int::from_nat(N::one()), pow2(bits)).expect("2^bits > 0")
+}
+
+// width(i) <= 2^-bits, without building 2^bits for a point.
+fn fits<N: Natural>(i: &Interval<N>, bits: usize) -> bool {
+    i.lo == i.hi || i.width() <= pow2_recip(bits)
+}
+
+// The smallest b with 2^b >= k.
+fn bits_for(k: usize) -> usize {
+    (usize::BITS - k.saturating_sub(1).leading_zeros()) as usize
+}
+
+// The smallest b with 2^b >= n.
+fn bits_of<N: Natural>(n: &N) -> usize {
+    let (two, mut p, mut b) = (N::from_usize(2), N::one(), 0);
+    while p < *n {
+        p = p.mul(&two);
+        b += 1;
+    }
+    b
+}
+
+// An integer bound on |x| for every x in i.
+fn magnitude<N: Natural>(i: &Interval<N>) -> N {
+    i.lo.abs().max(i.hi.abs()).ceil().abs_nat()
+}
+
+// i rounded outward onto the grid 2^-(b+2), adding at most 2^-(b+1) to its
+// width. Points are left exact.
+fn outward<N: Natural>(i: Interval<N>, b: usize) -> Interval<N> {
+    if i.lo == i.hi {
+        return i;
+    }
+    let grid = pow2(b + 2);
+    Interval::new(round_down(&i.lo, &grid), round_up(&i.hi, &grid))
+}
+
+// floor(r p) / p and ceil(r p) / p: r rounded outward to the grid 1/p, to
+// keep denominators from piling up inside series.
+fn round_down<N: Natural>(r: &Rat<N>, p: &N) -> Rat<N> {
+    let scaled = r.mul(&Rat::from_int(Int::from_nat(p.clone())));
+    Rat::new(scaled.floor(), p.clone()).expect("p >= 1")
+}
+
+fn round_up<N: Natural>(r: &Rat<N>, p: &N) -> Rat<N> {
+    let scaled = r.mul(&Rat::from_int(Int::from_nat(p.clone())));
+    Rat::new(scaled.ceil(), p.clone()).expect("p >= 1")
+}
+
+fn interval_mul<N: Natural>(a: &Interval<N>, b: &Interval<N>) -> Interval<N> {
     let ac = a.lo.mul(&b.lo);
     let ad = a.lo.mul(&b.hi);
     let bc = a.hi.mul(&b.lo);
@@ -173,7 +322,7 @@ fn interval_mul(a: &Interval, b: &Interval) -> Interval {
     Interval::new(lo, hi)
 }
 
-fn interval_inv(a: &Interval) -> Interval {
+fn interval_inv<N: Natural>(a: &Interval<N>) -> Interval<N> {
     assert!(!a.contains_zero(), "cannot invert interval containing 0");
     // For monotone 1/x on intervals that do not cross 0:
     // - if interval is positive: [1/hi, 1/lo]
@@ -187,3 +336,65 @@ fn interval_inv(a: &Interval) -> Interval {
         Ordering::Equal => unreachable!("contains_zero would have been true"),
     }
 }
+
+// Test helpers shared by the R submodules.
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::Real;
+    use crate::n::BinNat;
+    use crate::q::Rat;
+
+    pub(crate) fn real(s: &str) -> Real<BinNat> {
+        Real::from_rat(s.parse().unwrap())
+    }
+
+    // x at precision k encloses a value in [lo, hi] and has width <= 1/k.
+    pub(crate) fn near(x: &Real<BinNat>, k: usize, lo: &str, hi: &str) -> bool {
+        let i = x.interval(k);
+        let (lo, hi): (Rat<BinNat>, Rat<BinNat>) = (lo.parse().unwrap(), hi.parse().unwrap());
+        let width: Rat<BinNat> = format!("1/{}", k).parse().unwrap();
+        i.lo <= hi && lo <= i.hi && i.width() <= width
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::q::test_util::rat;
+
+    #[test]
+    fn real_operators_on_rational_points() {
+        let a = Real::from_rat(rat(1, 2));
+        let b = Real::from_rat(rat(1, 3));
+        assert_eq!(format!("{}", &a - &b), "1/6");
+        assert_eq!(format!("{}", -&a), "-1/2");
+
+        let q = (&a / &b).interval(4);
+        assert!(q.lo <= rat(3, 2) && rat(3, 2) <= q.hi);
+        assert!(q.width() <= rat(1, 4));
+
+        let total: Real = [a.clone(), b.clone(), a].into_iter().sum();
+        assert_eq!(format!("{}", total), "4/3");
+    }
+
+    #[test]
+    fn deep_expressions_keep_dyadic_endpoints() {
+        use crate::n::BinNat;
+        // 20 steps of the logistic map x -> 15/4 x (1 - x).
+        let r = Real::<BinNat>::from_rat("15/4".parse().unwrap());
+        let one = Real::from_rat(Rat::one());
+        let x = (0..20).fold(Real::sqrt2().sub(&one), |x, _| r.mul(&x).mul(&one.sub(&x)));
+        let i = x.approx(32);
+        assert!(i.width() <= pow2_recip(32));
+        // Denominators are powers of two no finer than the output grid.
+        for end in [&i.lo, &i.hi] {
+            assert!(pow2::<BinNat>(34).div_mod(&end.den).unwrap().1.is_zero());
+        }
+        let v: Rat<BinNat> = "0.2231746459705107675952".parse().unwrap();
+        assert!(i.lo <= v && v <= i.hi);
+        // interval(k) is approx(ceil(log2 k)).
+        assert_eq!(bits_for(1), 0);
+        assert_eq!(bits_for(1000), 10);
+        assert_eq!(bits_for(1024), 10);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::n::Natural;
+use crate::q::Rat;
+use crate::z::Int;
+
+use super::{Interval, Real, outward, pow2};
+
+// Irrational constants. Each enclosure is built from exact rational bounds,
+// not from floating point, so `approx(b)` really contains the constant:
+//
+// - sqrt(2), phi: from isqrt, m/k <= sqrt(n) < (m+1)/k with m = isqrt(n k^2);
+//   k = 2^b makes the ends dyadic already.
+// - e: sum 1/j! for j <= n; the tail is below 1/(n! n).
+// - ln 2: sum 1/(j 2^j) for j <= n; the tail is below 1/((n+1) 2^n).
+// - pi: Machin, pi = 16 atan(1/5) - 4 atan(1/239), each atan an alternating
+//   series bracketed by consecutive partial sums.
+impl<N: Natural> Real<N> {
+    pub fn sqrt2() -> Real<N> {
+        Real::from_fn(|b| sqrt_enclosure(&N::from_usize(2), &pow2(b)))
+    }
+
+    // The golden ratio (1 + sqrt(5)) / 2.
+    pub fn phi() -> Real<N> {
+        Real::from_fn(|b| {
+            let s = sqrt_enclosure(&N::from_usize(5), &pow2(b));
+            let half = frac(N::one(), N::from_usize(2));
+            let one = Rat::one();
+            Interval::new(s.lo.add(&one).mul(&half), s.hi.add(&one).mul(&half))
+        })
+    }
+
+    pub fn e() -> Real<N> {
+        Real::from_fn(|b| outward(e_enclosure(&pow2(b + 1)), b))
+    }
+
+    pub fn pi() -> Real<N> {
+        Real::from_fn(|b| outward(pi_enclosure(&pow2(b + 1)), b))
+    }
+
+    pub fn ln2() -> Real<N> {
+        Real::from_fn(|b| outward(ln2_enclosure(&pow2(b + 1)), b))
+    }
+}
+
+// e to width at most 1/k. (`exp` and `ln` need precisions beyond usize.)
+pub(super) fn e_enclosure<N: Natural>(k: &N) -> Interval<N> {
+    let mut sum = Rat::one();
+    let mut fact = N::one();
+    let mut n = N::zero();
+    loop {
+        n = n.succ();
+        fact = fact.mul(&n);
+        sum = sum.add(&frac(N::one(), fact.clone()));
+        let tail = fact.mul(&n);
+        if tail >= *k {
+            return Interval::new(sum.clone(), sum.add(&frac(N::one(), tail)));
+        }
+    }
+}
+
+// pi to width at most 1/k.
+pub(super) fn pi_enclosure<N: Natural>(k: &N) -> Interval<N> {
+    // Widths 1/(32k) and 1/(8k), so 16 * ... + 4 * ... <= 1/k.
+    let a = atan_inv(&N::from_usize(5), &k.mul(&N::from_usize(32)));
+    let b = atan_inv(&N::from_usize(239), &k.mul(&N::from_usize(8)));
+    let (c16, c4) = (nat(16), nat(4));
+    Interval::new(
+        a.lo.mul(&c16).sub(&b.hi.mul(&c4)),
+        a.hi.mul(&c16).sub(&b.lo.mul(&c4)),
+    )
+}
+
+// ln 2 to width at most 1/k.
+pub(super) fn ln2_enclosure<N: Natural>(k: &N) -> Interval<N> {
+    let two = N::from_usize(2);
+    let mut sum = Rat::zero();
+    let mut pow2 = N::one();
+    let mut n = N::zero();
+    loop {
+        n = n.succ();
+        pow2 = pow2.mul(&two);
+        sum = sum.add(&frac(N::one(), n.mul(&pow2)));
+        let tail = n.clone().succ().mul(&pow2);
+        if tail >= *k {
+            return Interval::new(sum.clone(), sum.add(&frac(N::one(), tail)));
+        }
+    }
+}
+
+// [m/k, (m+1)/k] around sqrt(n), with m = isqrt(n k^2).
+fn sqrt_enclosure<N: Natural>(n: &N, k: &N) -> Interval<N> {
+    let m = n.mul(k).mul(k).isqrt();
+    Interval::new(frac(m.clone(), k.clone()), frac(m.succ(), k.clone()))
+}
+
+// atan(1/x) for x >= 2, to width at most 1/m. The series
+//   atan(1/x) = 1/x - 1/(3x^3) + 1/(5x^5) - ...
+// alternates with decreasing terms, so the value lies between the partial
+// sum and the partial sum plus the next term.
+fn atan_inv<N: Natural>(x: &N, m: &N) -> Interval<N> {
+    let x2 = x.mul(x);
+    let two = N::from_usize(2);
+    let mut power = x.clone(); // x^(2n+1)
+    let mut odd = N::one(); // 2n+1
+    let mut sum = Rat::zero();
+    let mut subtract = false;
+    loop {
+        let term = frac(N::one(), odd.mul(&power));
+        sum = if subtract {
+            sum.sub(&term)
+        } else {
+            sum.add(&term)
+        };
+        subtract = !subtract;
+        power = power.mul(&x2);
+        odd = odd.add(&two);
+        let next = odd.mul(&power);
+        if next >= *m {
+            let next = frac(N::one(), next);
+            return if subtract {
+                Interval::new(sum.sub(&next), sum)
+            } else {
+                Interval::new(sum.clone(), sum.add(&next))
+            };
+        }
+    }
+}
+
+// a/b for naturals, b > 0.
+pub(super) fn frac<N: Natural>(a: N, b: N) -> Rat<N> {
+    Rat::new(Int::from_nat(a), b).expect("b != 0")
+}
+
+fn nat<N: Natural>(v: usize) -> Rat<N> {
+    Rat::from_int(Int::from_nat(N::from_usize(v)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::BinNat;
+
+    // Checks enclosures of x against its first 35 decimals. The true value
+    // lies in [d, d + 10^-35], so every interval must reach that range.
+    fn check(x: Real<BinNat>, digits: &str) {
+        let d: Rat<BinNat> = digits.parse().unwrap();
+        let eps: Rat<BinNat> = "1e-35".parse().unwrap();
+        for k in [1, 3, 16, 1000, 1_000_000_007, 1_000_000_000_000_000] {
+            let i = x.interval(k);
+            assert!(i.lo <= d.add(&eps) && d <= i.hi, "{} at k = {}", digits, k);
+            assert!(i.width() <= frac(BinNat::one(), BinNat::from_usize(k)));
+        }
+    }
+
+    #[test]
+    fn constants_match_known_digits() {
+        check(Real::sqrt2(), "1.41421356237309504880168872420969807");
+        check(Real::phi(), "1.61803398874989484820458683436563811");
+        check(Real::e(), "2.71828182845904523536028747135266249");
+        check(Real::pi(), "3.14159265358979323846264338327950288");
+        check(Real::ln2(), "0.69314718055994530941723212145817656");
+    }
+
+    #[test]
+    fn constants_combine_with_arithmetic() {
+        // phi^2 = phi + 1 and sqrt(2)^2 = 2, up to the enclosure widths.
+        let phi = Real::<BinNat>::phi();
+        let lhs = phi.mul(&phi).sub(&phi).interval(1_000_000);
+        assert!(lhs.lo <= Rat::one() && Rat::one() <= lhs.hi);
+        let two = Real::<BinNat>::sqrt2()
+            .mul(&Real::sqrt2())
+            .interval(1_000_000);
+        assert!(two.lo <= nat(2) && nat(2) <= two.hi);
+    }
+}
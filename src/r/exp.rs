@@ -0,0 +1,233 @@
+use crate::n::Natural;
+use crate::q::Rat;
+use crate::z::Int;
+
+use super::consts::{e_enclosure, frac, ln2_enclosure};
+use super::{Interval, Real, pow2, round_down, round_up};
+
+// Exponential and logarithm. Both are increasing, so an enclosure [lo, hi]
+// of x maps to [lower bound of f(lo), upper bound of f(hi)]; the ends are
+// first rounded outward to the grid 2^-q so the series below work with small
+// denominators.
+//
+// - exp(r): with n = floor(r) and f = r - n in [0, 1),
+//     exp(r) = e^n * sum_{j<=m} f^j / j!  +  tail,   tail <= e^n * 3/(m+1)!
+// - ln(r), r > 0: with r = 2^m y and y in [1, 2), z = (y-1)/(y+1) < 1/3,
+//     ln(r) = m ln 2 + 2 sum_{j<=t} z^(2j+1) / (2j+1)  +  tail,
+//   where tail <= 2 z^(2t+3) / (1 - z^2) <= (9/4) 3^-(2t+3).
+//
+// `ln` fails like `Real::div` on a zero divisor: the sign of a Real is
+// undecidable, so a nonpositive argument cannot always be rejected up front.
+// One that gets through refines forever instead of panicking.
+impl<N: Natural> Real<N> {
+    pub fn exp(&self) -> Real<N> {
+        self.refine_map(|i, q| {
+            let p = &pow2(q);
+            let lo = exp_enclosure(&round_down(&i.lo, p), p).lo;
+            let hi = exp_enclosure(&round_up(&i.hi, p), p).hi;
+            Some(Interval::new(lo, hi))
+        })
+    }
+
+    // The natural logarithm. None if x is already seen to be <= 0 at
+    // precision 1. Otherwise `interval` refines until the enclosure of x is
+    // positive, so for x <= 0 it refines forever, like `div` by zero.
+    pub fn ln(&self) -> Option<Real<N>> {
+        if self.interval(1).hi <= Rat::zero() {
+            return None;
+        }
+        Some(self.refine_map(ln_interval))
+    }
+
+    // log_base(x) = ln x / ln base. Fails like `ln` on either argument; a
+    // base of 1 divides by ln 1 = 0, which refines forever like `div`.
+    pub fn log_base(&self, base: &Real<N>) -> Option<Real<N>> {
+        self.ln()?.div(&base.ln()?)
+    }
+
+    // x^y = exp(y ln x) for x > 0, failing like `ln` otherwise. For rational
+    // exponents of any sign, `Rat::pow` and `nth_root` are exact.
+    pub fn pow(&self, exp: &Real<N>) -> Option<Real<N>> {
+        Some(self.ln()?.mul(exp).exp())
+    }
+}
+
+// exp(r), narrowing as p grows.
+fn exp_enclosure<N: Natural>(r: &Rat<N>, p: &N) -> Interval<N> {
+    let n = r.floor();
+    let f = r.sub(&Rat::from_int(n.clone()));
+    // e^n grows the error; ask for about 3^n times more precision.
+    let boost = if n.is_negative() {
+        N::one()
+    } else {
+        N::from_usize(3).pow(&n.abs_nat().succ())
+    };
+    let prec = p.mul(&boost);
+
+    // f >= 0, so every term is too; each is rounded outward to the grid
+    // 1/g, which keeps the denominators at g instead of growing like f^j.
+    let g = prec.mul(&N::from_usize(1024));
+    let (mut lo, mut hi) = (Rat::one(), Rat::one());
+    let (mut term_lo, mut term_hi) = (Rat::one(), Rat::one());
+    let mut j = N::zero();
+    let mut fact = N::one(); // (j+1)!
+    loop {
+        j = j.succ();
+        let step = f.mul(&frac(N::one(), j.clone()));
+        term_lo = round_down(&term_lo.mul(&step), &g);
+        term_hi = round_up(&term_hi.mul(&step), &g);
+        lo = lo.add(&term_lo);
+        hi = hi.add(&term_hi);
+        fact = fact.mul(&j.clone().succ());
+        if fact >= prec {
+            break;
+        }
+    }
+    let tail = frac(N::from_usize(3), fact);
+
+    let m = n.abs_nat();
+    let e = e_enclosure(&prec.mul(&m.clone().succ()));
+    let (e_lo, e_hi) = (power(&e.lo, &n), power(&e.hi, &n));
+    let (e_lo, e_hi) = if n.is_negative() {
+        (e_hi, e_lo)
+    } else {
+        (e_lo, e_hi)
+    };
+    Interval::new(e_lo.mul(&lo), e_hi.mul(&hi.add(&tail)))
+}
+
+// ln on an enclosure i of x at precision q; None until i is rounded down
+// to a positive lower end.
+fn ln_interval<N: Natural>(i: &Interval<N>, q: usize) -> Option<Interval<N>> {
+    let p = &pow2(q);
+    let lo = round_down(&i.lo, p);
+    if lo <= Rat::zero() {
+        return None;
+    }
+    let lo = ln_enclosure(&lo, p).lo;
+    let hi = ln_enclosure(&round_up(&i.hi, p), p).hi;
+    Some(Interval::new(lo, hi))
+}
+
+// ln(r) for r > 0, narrowing as p grows.
+fn ln_enclosure<N: Natural>(r: &Rat<N>, p: &N) -> Interval<N> {
+    let one = Rat::one();
+    let two = Rat::from_int(Int::from_nat(N::from_usize(2)));
+    let mut y = r.clone();
+    let mut m = Int::zero();
+    while y >= two {
+        y = y.div(&two).expect("2 != 0");
+        m = m.add(&Int::from_nat(N::one()));
+    }
+    while y < one {
+        y = y.mul(&two);
+        m = m.sub(&Int::from_nat(N::one()));
+    }
+
+    let prec = p.clone();
+    let z = y.sub(&one).div(&y.add(&one)).expect("y + 1 > 0");
+    let z2 = z.mul(&z);
+    let nine = N::from_usize(9);
+    // z >= 0; the powers and terms are rounded outward like in `exp`.
+    let g = prec.mul(&N::from_usize(1024));
+    let (mut lo, mut hi) = (Rat::zero(), Rat::zero());
+    let (mut power_lo, mut power_hi) = (z.clone(), z); // z^(2j+1)
+    let mut odd = N::one(); // 2j+1
+    let mut pow3 = N::from_usize(3); // 3^(2j+1)
+    loop {
+        let inv = frac(N::one(), odd.clone());
+        lo = lo.add(&round_down(&power_lo.mul(&inv), &g));
+        hi = hi.add(&round_up(&power_hi.mul(&inv), &g));
+        power_lo = round_down(&power_lo.mul(&z2), &g);
+        power_hi = round_up(&power_hi.mul(&z2), &g);
+        odd = odd.succ().succ();
+        pow3 = pow3.mul(&nine);
+        if pow3 >= prec {
+            break;
+        }
+    }
+    let tail = frac(nine, N::from_usize(4).mul(&pow3));
+
+    let l = ln2_enclosure(&prec.mul(&m.abs_nat().succ()));
+    let (l_lo, l_hi) = (
+        Rat::from_int(m.clone()).mul(&l.lo),
+        Rat::from_int(m.clone()).mul(&l.hi),
+    );
+    let (l_lo, l_hi) = if m.is_negative() {
+        (l_hi, l_lo)
+    } else {
+        (l_lo, l_hi)
+    };
+    Interval::new(l_lo.add(&lo).add(&lo), l_hi.add(&hi).add(&hi).add(&tail))
+}
+
+// x^n for x > 0.
+fn power<N: Natural>(x: &Rat<N>, n: &Int<N>) -> Rat<N> {
+    x.pow(n).expect("x > 0")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::BinNat;
+    use crate::r::test_util::{near, real};
+
+    #[test]
+    fn exp_enclosures() {
+        for k in [1, 100, 1_000_000_000] {
+            assert!(near(&real("0").exp(), k, "1", "1"));
+            assert!(near(&real("1").exp(), k, "2.7182818284", "2.7182818285"));
+            assert!(near(&real("-5/2").exp(), k, "0.0820849986", "0.0820849987"));
+            assert!(near(&real("10").exp(), k, "22026.4657948", "22026.4657949"));
+            // exp(sqrt 2), from an irrational argument.
+            let x = Real::<BinNat>::sqrt2().exp();
+            assert!(near(&x, k, "4.1132503787", "4.1132503788"));
+        }
+    }
+
+    #[test]
+    fn ln_enclosures() {
+        for k in [1, 100, 1_000_000] {
+            let ln10 = real("10").ln().unwrap();
+            assert!(near(&ln10, k, "2.3025850929", "2.3025850930"));
+            let ln_tenth = real("1/10").ln().unwrap();
+            assert!(near(&ln_tenth, k, "-2.3025850930", "-2.3025850929"));
+            assert!(near(&real("1").ln().unwrap(), k, "0", "0"));
+            assert!(near(&Real::e().ln().unwrap(), k, "1", "1"));
+            // exp and ln are inverse.
+            let x = real("7").ln().unwrap().exp();
+            assert!(near(&x, k, "7", "7"));
+        }
+        assert!(real("0").ln().is_none());
+        assert!(real("-3").ln().is_none());
+    }
+
+    #[test]
+    fn ln_of_a_small_negative_real_refines_instead_of_panicking() {
+        // 1.39 - sqrt(2) < 0, but its interval at precision 1 still reaches
+        // above 0, so the up-front check lets it through.
+        let x = real("1.39").sub(&Real::sqrt2());
+        assert!(x.ln().is_some());
+        assert!(real("1").add(&x).atanh().is_some());
+        // Its enclosures never separate from 0 on the positive side, so the
+        // interval step keeps asking for more bits rather than failing.
+        for q in [4, 16, 64] {
+            assert_eq!(ln_interval(&x.approx(q), q), None);
+        }
+        let straddling = Interval::new("-1/8".parse().unwrap(), "1/8".parse().unwrap());
+        assert_eq!(ln_interval::<BinNat>(&straddling, 8), None);
+    }
+
+    #[test]
+    fn log_base_and_pow() {
+        let k = 1_000_000;
+        let log2_8 = real("8").log_base(&real("2")).unwrap();
+        assert!(near(&log2_8, k, "3", "3"));
+        let root2 = real("2").pow(&real("1/2")).unwrap();
+        assert!(near(&root2, k, "1.414213562", "1.414213563"));
+        // 2^pi.
+        let x = real("2").pow(&Real::pi()).unwrap();
+        assert!(near(&x, k, "8.824977827", "8.824977828"));
+        assert!(real("-2").pow(&real("2")).is_none());
+    }
+}
@@ -0,0 +1,83 @@
+use crate::n::Natural;
+use crate::q::Rat;
+
+use super::Real;
+use super::consts::frac;
+
+// Hyperbolic functions, built from `exp`, `ln` and `sqrt`:
+//   sinh x = (e^x - e^-x) / 2        asinh x = ln(x + sqrt(x^2 + 1))
+//   cosh x = (e^x + e^-x) / 2        acosh x = ln(x + sqrt(x^2 - 1))
+//   tanh x = sinh x / cosh x         atanh x = (ln(1 + x) - ln(1 - x)) / 2
+impl<N: Natural> Real<N> {
+    pub fn sinh(&self) -> Real<N> {
+        self.exp().sub(&self.neg().exp()).mul(&half())
+    }
+
+    pub fn cosh(&self) -> Real<N> {
+        self.exp().add(&self.neg().exp()).mul(&half())
+    }
+
+    pub fn tanh(&self) -> Real<N> {
+        self.sinh().div(&self.cosh()).expect("cosh >= 1")
+    }
+
+    pub fn asinh(&self) -> Real<N> {
+        let one = Real::from_rat(Rat::one());
+        let arg = self.add(&self.mul(self).add(&one).sqrt());
+        arg.ln().expect("x + sqrt(x^2 + 1) > 0")
+    }
+
+    // Clamps x to [1, oo) like `asin` clamps to [-1, 1].
+    pub fn acosh(&self) -> Real<N> {
+        let x = self.max_rat(&Rat::one());
+        let one = Real::from_rat(Rat::one());
+        let arg = x.add(&x.mul(&x).sub(&one).sqrt());
+        arg.ln().expect("x + sqrt(x^2 - 1) >= 1")
+    }
+
+    // Defined on (-1, 1); fails like `ln` on 1 + x or 1 - x otherwise.
+    pub fn atanh(&self) -> Option<Real<N>> {
+        let one = Real::from_rat(Rat::one());
+        let up = one.add(self).ln()?;
+        let down = one.sub(self).ln()?;
+        Some(up.sub(&down).mul(&half()))
+    }
+}
+
+fn half<N: Natural>() -> Real<N> {
+    Real::from_rat(frac(N::one(), N::from_usize(2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::r::test_util::{near, real};
+
+    #[test]
+    fn hyperbolic_functions() {
+        for k in [1, 1000, 1_000_000] {
+            assert!(near(&real("1").sinh(), k, "1.175201193", "1.175201194"));
+            assert!(near(&real("1").cosh(), k, "1.543080634", "1.543080635"));
+            assert!(near(&real("1/2").tanh(), k, "0.462117157", "0.462117158"));
+            assert!(near(&real("0").sinh(), k, "0", "0"));
+        }
+        // cosh^2 - sinh^2 = 1.
+        let x = real("3/2");
+        let (s, c) = (x.sinh(), x.cosh());
+        assert!(near(&c.mul(&c).sub(&s.mul(&s)), 1000, "1", "1"));
+    }
+
+    #[test]
+    fn inverse_hyperbolic_functions() {
+        for k in [1, 1000, 1_000_000] {
+            assert!(near(&real("1").asinh(), k, "0.881373587", "0.881373588"));
+            assert!(near(&real("-1").asinh(), k, "-0.881373588", "-0.881373587"));
+            assert!(near(&real("2").acosh(), k, "1.316957896", "1.316957897"));
+            let atanh = real("1/2").atanh().unwrap();
+            assert!(near(&atanh, k, "0.549306144", "0.549306145"));
+        }
+        // Below 1, acosh clamps to acosh(1) = 0.
+        assert!(near(&real("1/2").acosh(), 1000, "0", "0"));
+        assert!(real("1").atanh().is_none());
+        assert!(real("-3").atanh().is_none());
+    }
+}
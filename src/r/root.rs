@@ -0,0 +1,130 @@
+use crate::n::Natural;
+use crate::q::Rat;
+use crate::z::Int;
+
+use super::{Interval, Real, pow2};
+
+// Roots of arbitrary reals. x^(1/n) is monotone, so an enclosure [lo, hi] of
+// x maps to [root(lo), root(hi)]; each end is rounded outward to the grid
+// 1/q by a certified integer bisection (`Natural::nth_root`):
+//   floor(a^(1/n) q) = nth_root(floor(a q^n))   for a >= 0.
+//
+// The sign of a Real is not decidable, so even roots cannot reject negative
+// inputs up front. Instead they clamp: sqrt(x) = sqrt(max(x, 0)). An
+// enclosure straddling zero then gives [0, root(hi)], which still narrows as
+// x is refined, and a negative x gives 0.
+impl<N: Natural> Real<N> {
+    // The nonnegative square root, clamped to 0 for negative x.
+    pub fn sqrt(&self) -> Real<N> {
+        self.nth_root(&N::from_usize(2)).expect("2 != 0")
+    }
+
+    // x^(1/n). Odd roots are defined on all of R (cbrt(-8) = -2); even roots
+    // clamp like `sqrt`. None for n = 0.
+    pub fn nth_root(&self, n: &N) -> Option<Real<N>> {
+        if n.is_zero() {
+            return None;
+        }
+        let n = n.clone();
+        let odd = !n.div_mod(&N::from_usize(2)).expect("2 != 0").1.is_zero();
+        // Rounding to the grid 2^-(q+2) costs at most 2^-(q+1).
+        Some(self.refine_map(move |i, q| {
+            let q = pow2(q + 2);
+            Some(Interval::new(
+                root_below(&i.lo, &n, &q, odd),
+                root_above(&i.hi, &n, &q, odd),
+            ))
+        }))
+    }
+}
+
+// A rational at most a^(1/n) and within 1/q of it.
+fn root_below<N: Natural>(a: &Rat<N>, n: &N, q: &N, odd: bool) -> Rat<N> {
+    match (a.is_negative(), odd) {
+        (false, _) => grid_root(a, n, q),
+        (true, true) => root_above(&a.neg(), n, q, odd).neg(),
+        (true, false) => Rat::zero(),
+    }
+}
+
+// A rational at least a^(1/n) and within 1/q of it.
+fn root_above<N: Natural>(a: &Rat<N>, n: &N, q: &N, odd: bool) -> Rat<N> {
+    match (a.is_negative(), odd) {
+        (false, _) => {
+            let step = Rat::new(Int::from_nat(N::one()), q.clone()).expect("q > 0");
+            grid_root(a, n, q).add(&step)
+        }
+        (true, true) => root_below(&a.neg(), n, q, odd).neg(),
+        (true, false) => Rat::zero(),
+    }
+}
+
+// floor(a^(1/n) q) / q for a >= 0.
+fn grid_root<N: Natural>(a: &Rat<N>, n: &N, q: &N) -> Rat<N> {
+    let scaled = a.mul(&Rat::from_int(Int::from_nat(q.pow(n))));
+    let m = scaled.floor().abs_nat().nth_root(n).expect("n != 0");
+    Rat::new(Int::from_nat(m), q.clone()).expect("q > 0")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::BinNat;
+    use crate::r::test_util::{near, real};
+
+    #[test]
+    fn roots_of_rationals() {
+        let n = BinNat::from_usize;
+        for k in [1, 10, 1_000_000, 1_000_000_000_000] {
+            assert!(near(&real("9/4").sqrt(), k, "3/2", "3/2"));
+            assert!(near(
+                &real("-27/8").nth_root(&n(3)).unwrap(),
+                k,
+                "-3/2",
+                "-3/2"
+            ));
+            assert!(near(&real("1024").nth_root(&n(10)).unwrap(), k, "2", "2"));
+            assert!(near(&real("0").sqrt(), k, "0", "0"));
+        }
+        // Agrees with the constant: both enclose the same digits.
+        let s = real("2").sqrt().interval(1_000_000_000_000);
+        let c = Real::<BinNat>::sqrt2().interval(1_000_000_000_000);
+        assert!(s.lo <= c.hi && c.lo <= s.hi);
+        assert_eq!(real("2").nth_root(&n(0)).map(|_| ()), None);
+    }
+
+    #[test]
+    fn roots_of_irrationals() {
+        // 2^(1/4) = sqrt(sqrt(2)) and cbrt(pi).
+        let fourth = Real::<BinNat>::sqrt2().sqrt();
+        let cbrt_pi = Real::<BinNat>::pi()
+            .nth_root(&BinNat::from_usize(3))
+            .unwrap();
+        for k in [7, 1_000_000_000] {
+            let i = fourth.interval(k);
+            assert!(i.lo <= "1.189207116".parse().unwrap());
+            assert!(i.hi >= "1.189207115".parse().unwrap());
+            let i = cbrt_pi.interval(k);
+            assert!(i.lo <= "1.464591888".parse().unwrap());
+            assert!(i.hi >= "1.464591887".parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn even_roots_clamp_at_zero() {
+        // x - x straddles zero at every precision; the root still converges.
+        let x = Real::<BinNat>::sqrt2();
+        let zero = x.sub(&x).sqrt();
+        for k in [1, 100, 10_000] {
+            assert!(near(&zero, k, "0", "0"));
+        }
+        // A negative input clamps to 0; odd roots keep the sign.
+        assert!(near(&real("-4").sqrt(), 1000, "0", "0"));
+        assert!(near(
+            &real("-8").nth_root(&BinNat::from_usize(3)).unwrap(),
+            1000,
+            "-2",
+            "-2"
+        ));
+    }
+}
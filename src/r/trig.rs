@@ -0,0 +1,296 @@
+use crate::n::Natural;
+use crate::q::Rat;
+use crate::z::{Int, Rounding};
+
+use super::consts::{frac, pi_enclosure};
+use super::{Interval, Real, pow2, round_down, round_up};
+
+// Circular functions and their inverses.
+//
+// sin and cos are not monotone, but they are 1-Lipschitz: on an enclosure
+// [lo, hi] of x they stay within r = hi - c of their value at a point c in
+// it. That value comes from range reduction by a certified pi,
+//   c = j pi/2 + t,   |t| <= pi/4 (up to the width of the pi enclosure),
+// and the alternating Taylor series of sin t or cos t, which for |t| < 1 is
+// bracketed by consecutive partial sums.
+//
+// atan is increasing, so its ends are bounded separately. For 0 <= x <= 1,
+// Euler's series
+//   atan(x) = sum_n 2^(2n) (n!)^2 / (2n+1)! * x^(2n+1) / (1+x^2)^(n+1)
+// has positive terms shrinking by at least 1/2 each, so the tail is at most
+// twice the first omitted term. Larger x use atan(x) = pi/2 - atan(1/x).
+//
+// Like even roots, asin and acos clamp their argument to [-1, 1], since
+// |x| <= 1 is undecidable for a Real.
+impl<N: Natural> Real<N> {
+    pub fn sin(&self) -> Real<N> {
+        self.refine_map(|i, q| Some(lipschitz(i, q, |c, prec| sin_quarter(c, prec, 0))))
+    }
+
+    pub fn cos(&self) -> Real<N> {
+        // cos(x) = sin(x + pi/2): one quarter turn further.
+        self.refine_map(|i, q| Some(lipschitz(i, q, |c, prec| sin_quarter(c, prec, 1))))
+    }
+
+    // sin / cos. Same partiality as Real::div: this is always Some, and at
+    // the poles (odd multiples of pi/2), where cos is 0, `interval` never
+    // terminates instead of giving None.
+    pub fn tan(&self) -> Option<Real<N>> {
+        self.sin().div(&self.cos())
+    }
+
+    pub fn atan(&self) -> Real<N> {
+        self.refine_map(|i, q| {
+            let g = pow2(q + 2);
+            let lo = atan_rat(&round_down(&i.lo, &g), &g).lo;
+            let hi = atan_rat(&round_up(&i.hi, &g), &g).hi;
+            Some(Interval::new(lo, hi))
+        })
+    }
+
+    // asin(x) = 2 atan(x / (1 + sqrt(1 - x^2))), which stays finite at
+    // x = +-1.
+    pub fn asin(&self) -> Real<N> {
+        let one = Rat::one();
+        let x = self.max_rat(&one.neg()).min_rat(&one);
+        let one = Real::from_rat(one);
+        let den = one.add(&one.sub(&x.mul(&x)).sqrt());
+        let half = x.div(&den).expect("1 + sqrt(...) >= 1").atan();
+        half.add(&half)
+    }
+
+    pub fn acos(&self) -> Real<N> {
+        half_pi().sub(&self.asin())
+    }
+
+    // The angle of the point (x, y) = (other, self), in (-pi, pi], via
+    //   atan2(y, x) = 2 atan(y / (sqrt(x^2 + y^2) + x)).
+    // Same partiality as Real::div: this is always Some. The angle jumps
+    // across the negative x-axis, so for atan2(0, x) with x <= 0 (including
+    // the origin) `interval` never terminates instead of giving None.
+    pub fn atan2(&self, other: &Real<N>) -> Option<Real<N>> {
+        let (y, x) = (self, other);
+        let r = x.mul(x).add(&y.mul(y)).sqrt();
+        let half = y.div(&r.add(x))?.atan();
+        Some(half.add(&half))
+    }
+}
+
+fn half_pi<N: Natural>() -> Real<N> {
+    Real::pi().mul(&Real::from_rat(frac(N::one(), N::from_usize(2))))
+}
+
+// f on the enclosure i, for a 1-Lipschitz f given as f(c, prec): a point
+// c of i on the grid 2^-(q+2), widened by the distance to the far end.
+fn lipschitz<N: Natural>(
+    i: &Interval<N>,
+    q: usize,
+    f: impl Fn(&Rat<N>, &N) -> Interval<N>,
+) -> Interval<N> {
+    let g = pow2(q + 2);
+    let mid = i.lo.add(&i.hi).mul(&frac(N::one(), N::from_usize(2)));
+    let c = round_down(&mid, &g).max(i.lo.clone());
+    let r = i.hi.sub(&c);
+    let v = f(&c, &g);
+    Interval::new(v.lo.sub(&r), v.hi.add(&r))
+}
+
+// sin(c + shift pi/2) for rational c, narrowing as prec grows.
+fn sin_quarter<N: Natural>(c: &Rat<N>, prec: &N, shift: usize) -> Interval<N> {
+    // Enough digits of pi that j pi/2 is still accurate for |j| ~ |c|.
+    let mag = c.abs().ceil().abs_nat().succ();
+    let pi = pi_enclosure(&prec.mul(&mag).mul(&N::from_usize(16)));
+    let half = frac(N::one(), N::from_usize(2));
+    let (h_lo, h_hi) = (pi.lo.mul(&half), pi.hi.mul(&half));
+
+    let j = c.div(&h_lo).expect("pi > 0").round(Rounding::HalfEven);
+    let jr = Rat::from_int(j.clone());
+    let (t_lo, t_hi) = if j.is_negative() {
+        (c.sub(&jr.mul(&h_lo)), c.sub(&jr.mul(&h_hi)))
+    } else {
+        (c.sub(&jr.mul(&h_hi)), c.sub(&jr.mul(&h_lo)))
+    };
+    // sin and cos are 1-Lipschitz in t as well.
+    let t = round_down(&t_lo, prec);
+    let w = round_up(&t_hi, prec).sub(&t);
+
+    let quarter = j
+        .add(&Int::from_nat(N::from_usize(shift)))
+        .rem_euclid(&Int::from_nat(N::from_usize(4)))
+        .expect("4 != 0")
+        .to_usize();
+    let t2 = t.mul(&t);
+    let v = match quarter {
+        0 => alternating(t.clone(), &t2, 1, prec),
+        1 => alternating(Rat::one(), &t2, 0, prec),
+        2 => negate(alternating(t.clone(), &t2, 1, prec)),
+        _ => negate(alternating(Rat::one(), &t2, 0, prec)),
+    };
+    Interval::new(v.lo.sub(&w), v.hi.add(&w))
+}
+
+// sum_j (-1)^j a_j with a_0 = first and
+//   a_(j+1) = a_j t^2 / ((o+2j+1)(o+2j+2)),
+// i.e. cos t for first = 1, o = 0 and sin t for first = t, o = 1. For
+// t^2 <= 1 the terms shrink, so the sum lies within the next term of a
+// partial sum; stops once that term is at most 1/prec. The terms are
+// rounded outward to the grid 1/(1024 prec), which keeps their
+// denominators from growing like t^(2j).
+fn alternating<N: Natural>(first: Rat<N>, t2: &Rat<N>, offset: usize, prec: &N) -> Interval<N> {
+    if first.is_negative() {
+        return negate(alternating(first.neg(), t2, offset, prec));
+    }
+    let eps = frac(N::one(), prec.clone());
+    let g = prec.mul(&N::from_usize(1024));
+    let (mut lo, mut hi) = (Rat::zero(), Rat::zero());
+    let (mut term_lo, mut term_hi) = (first.clone(), first);
+    let mut n = N::from_usize(offset);
+    let mut subtract = false;
+    loop {
+        if subtract {
+            lo = lo.sub(&term_hi);
+            hi = hi.sub(&term_lo);
+        } else {
+            lo = lo.add(&term_lo);
+            hi = hi.add(&term_hi);
+        }
+        subtract = !subtract;
+        let a = n.clone().succ();
+        let b = a.clone().succ();
+        let step = t2.mul(&frac(N::one(), a.mul(&b)));
+        term_lo = round_down(&term_lo.mul(&step), &g);
+        term_hi = round_up(&term_hi.mul(&step), &g);
+        n = b;
+        if term_hi <= eps {
+            return Interval::new(lo.sub(&term_hi), hi.add(&term_hi));
+        }
+    }
+}
+
+fn negate<N: Natural>(i: Interval<N>) -> Interval<N> {
+    Interval::new(i.hi.neg(), i.lo.neg())
+}
+
+// atan(r) for rational r, narrowing as prec grows.
+fn atan_rat<N: Natural>(r: &Rat<N>, prec: &N) -> Interval<N> {
+    if r.is_negative() {
+        return negate(atan_rat(&r.neg(), prec));
+    }
+    if *r > Rat::one() {
+        let v = atan_euler(&Rat::one().div(r).expect("r > 1"), prec);
+        let pi = pi_enclosure(&prec.mul(&N::from_usize(2)));
+        let half = frac(N::one(), N::from_usize(2));
+        return Interval::new(pi.lo.mul(&half).sub(&v.hi), pi.hi.mul(&half).sub(&v.lo));
+    }
+    atan_euler(r, prec)
+}
+
+// Euler's series for 0 <= x <= 1: a_0 = x/(1+x^2) and
+//   a_(n+1) = a_n (2n+2)/(2n+3) x^2/(1+x^2),   x^2/(1+x^2) <= 1/2.
+fn atan_euler<N: Natural>(x: &Rat<N>, prec: &N) -> Interval<N> {
+    let x2 = x.mul(x);
+    let d = Rat::one().add(&x2);
+    let y = x2.div(&d).expect("1 + x^2 > 0");
+    let eps = frac(N::one(), prec.mul(&N::from_usize(2)));
+    // The terms are positive; they are rounded outward like in `alternating`.
+    let g = prec.mul(&N::from_usize(1024));
+    let first = x.div(&d).expect("1 + x^2 > 0");
+    let (mut term_lo, mut term_hi) = (round_down(&first, &g), round_up(&first, &g));
+    let (mut lo, mut hi) = (Rat::zero(), Rat::zero());
+    let mut even = N::zero(); // 2n
+    loop {
+        lo = lo.add(&term_lo);
+        hi = hi.add(&term_hi);
+        even = even.succ().succ();
+        let step = y.mul(&frac(even.clone(), even.clone().succ()));
+        term_lo = round_down(&term_lo.mul(&step), &g);
+        term_hi = round_up(&term_hi.mul(&step), &g);
+        if term_hi <= eps {
+            return Interval::new(lo, hi.add(&term_hi).add(&term_hi));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::BinNat;
+    use crate::r::test_util::{near, real};
+
+    #[test]
+    fn sin_cos_tan() {
+        let pi = Real::<BinNat>::pi();
+        for k in [1, 1000, 1_000_000_000] {
+            assert!(near(&real("0").sin(), k, "0", "0"));
+            assert!(near(&real("0").cos(), k, "1", "1"));
+            assert!(near(&real("1").sin(), k, "0.8414709848", "0.8414709849"));
+            assert!(near(&real("1").cos(), k, "0.5403023058", "0.5403023059"));
+            assert!(near(&real("-2").cos(), k, "-0.4161468366", "-0.4161468365"));
+            // Range reduction by many quarter turns.
+            assert!(near(
+                &real("100").sin(),
+                k,
+                "-0.5063656412",
+                "-0.5063656411"
+            ));
+            assert!(near(&pi.div(&real("6")).unwrap().sin(), k, "0.5", "0.5"));
+            assert!(near(&pi.cos(), k, "-1", "-1"));
+            let tan = real("1").tan().unwrap();
+            assert!(near(&tan, k, "1.5574077246", "1.5574077247"));
+        }
+        // sin^2 + cos^2 = 1 at an irrational point.
+        let x = Real::<BinNat>::sqrt2();
+        let (s, c) = (x.sin(), x.cos());
+        assert!(near(&s.mul(&s).add(&c.mul(&c)), 1_000_000, "1", "1"));
+    }
+
+    #[test]
+    fn inverse_functions() {
+        for k in [1, 1000, 1_000_000] {
+            assert!(near(&real("1").atan(), k, "0.7853981633", "0.7853981634"));
+            assert!(near(
+                &real("-3").atan(),
+                k,
+                "-1.2490457724",
+                "-1.2490457723"
+            ));
+            assert!(near(&real("1/2").asin(), k, "0.5235987755", "0.5235987756"));
+            assert!(near(&real("0").acos(), k, "1.5707963267", "1.5707963268"));
+        }
+        // At +-1 asin has a square-root singularity: precision 1/k needs the
+        // argument to about 1/k^2, so keep k small there.
+        assert!(near(
+            &real("1").asin(),
+            1000,
+            "1.5707963267",
+            "1.5707963268"
+        ));
+        assert!(near(
+            &real("-1").acos(),
+            1000,
+            "3.1415926535",
+            "3.1415926536"
+        ));
+        // Outside [-1, 1] the argument is clamped.
+        assert!(near(
+            &real("3").asin(),
+            1000,
+            "1.5707963267",
+            "1.5707963268"
+        ));
+        // sin(atan(x)) = x / sqrt(1 + x^2): 2 / sqrt(5).
+        let x = real("2").atan().sin();
+        assert!(near(&x, 1_000_000, "0.894427190", "0.894427191"));
+    }
+
+    #[test]
+    fn atan2_quadrants() {
+        let k = 100_000;
+        let angle = |y: &str, x: &str| real(y).atan2(&real(x)).unwrap();
+        assert!(near(&angle("0", "1"), k, "0", "0"));
+        assert!(near(&angle("1", "1"), k, "0.785398163", "0.785398164"));
+        assert!(near(&angle("1", "-1"), k, "2.356194490", "2.356194491"));
+        assert!(near(&angle("-1", "-1"), k, "-2.356194491", "-2.356194490"));
+        assert!(near(&angle("-2", "0"), k, "-1.570796327", "-1.570796326"));
+    }
+}
@@ -1,29 +1,39 @@
 use std::cmp::Ordering;
 use std::fmt;
 
-use crate::n::Nat;
+use crate::n::{Nat, Natural};
+
+mod div;
+mod euclid;
+mod pair;
+
+pub use div::{DivisionByZero, Rounding};
+pub use euclid::{LinearSolution, crt, solve_linear_diophantine};
+pub use pair::IntPair;
 
 // Integers (Z) built on top of N. We normalize to a canonical form:
 // - Zero
 // - Pos(n) where n > 0
 // - Neg(n) where n > 0
+//
+// Generic over the naturals backend; defaults to the Peano `Nat`.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum Int {
-    Neg(Nat),
+pub enum Int<N = Nat> {
+    Neg(N),
     Zero,
-    Pos(Nat),
+    Pos(N),
 }
 
-impl Int {
-    pub fn zero() -> Int {
+impl<N: Natural> Int<N> {
+    pub fn zero() -> Int<N> {
         Int::Zero
     }
 
-    pub fn from_nat(n: Nat) -> Int {
+    pub fn from_nat(n: N) -> Int<N> {
         if n.is_zero() { Int::Zero } else { Int::Pos(n) }
     }
 
-    pub fn neg(&self) -> Int {
+    pub fn neg(&self) -> Int<N> {
         match self {
             Int::Zero => Int::Zero,
             Int::Pos(n) => Int::Neg(n.clone()),
@@ -31,9 +41,9 @@ impl Int {
         }
     }
 
-    pub fn abs_nat(&self) -> Nat {
+    pub fn abs_nat(&self) -> N {
         match self {
-            Int::Zero => Nat::Zero,
+            Int::Zero => N::zero(),
             Int::Pos(n) | Int::Neg(n) => n.clone(),
         }
     }
@@ -46,7 +56,7 @@ impl Int {
         matches!(self, Int::Neg(_))
     }
 
-    pub fn from_diff(pos: Nat, neg: Nat) -> Int {
+    pub fn from_diff(pos: N, neg: N) -> Int<N> {
         // Represents pos - neg, normalize to canonical enum.
         match pos.cmp(&neg) {
             std::cmp::Ordering::Equal => Int::Zero,
@@ -61,26 +71,26 @@ impl Int {
         }
     }
 
-    fn as_diff(&self) -> (Nat, Nat) {
+    fn as_diff(&self) -> (N, N) {
         // Return (pos, neg) meaning pos - neg.
         match self {
-            Int::Zero => (Nat::Zero, Nat::Zero),
-            Int::Pos(n) => (n.clone(), Nat::Zero),
-            Int::Neg(n) => (Nat::Zero, n.clone()),
+            Int::Zero => (N::zero(), N::zero()),
+            Int::Pos(n) => (n.clone(), N::zero()),
+            Int::Neg(n) => (N::zero(), n.clone()),
         }
     }
 
-    pub fn add(&self, other: &Int) -> Int {
+    pub fn add(&self, other: &Int<N>) -> Int<N> {
         let (a_pos, a_neg) = self.as_diff();
         let (b_pos, b_neg) = other.as_diff();
         Int::from_diff(a_pos.add(&b_pos), a_neg.add(&b_neg))
     }
 
-    pub fn sub(&self, other: &Int) -> Int {
+    pub fn sub(&self, other: &Int<N>) -> Int<N> {
         self.add(&other.neg())
     }
 
-    pub fn mul(&self, other: &Int) -> Int {
+    pub fn mul(&self, other: &Int<N>) -> Int<N> {
         // (a-b)(c-d) = (ac + bd) - (ad + bc)
         let (a, b) = self.as_diff();
         let (c, d) = other.as_diff();
@@ -91,7 +101,7 @@ impl Int {
         Int::from_diff(ac.add(&bd), ad.add(&bc))
     }
 
-    pub fn cmp_int(&self, other: &Int) -> Ordering {
+    pub fn cmp_int(&self, other: &Int<N>) -> Ordering {
         match (self, other) {
             (Int::Neg(a), Int::Neg(b)) => b.cmp(a), // more negative = smaller
             (Int::Neg(_), Int::Zero) => Ordering::Less,
@@ -106,19 +116,93 @@ impl Int {
     }
 }
 
-impl PartialOrd for Int {
+// Powers and roots, lifted from the naturals by tracking the sign.
+impl<N: Natural> Int<N> {
+    // self^exp. Negative bases give a negative result for odd exponents.
+    pub fn pow(&self, exp: &N) -> Int<N> {
+        let m = Int::from_nat(self.abs_nat().pow(exp));
+        if self.is_negative() && is_odd(exp) {
+            m.neg()
+        } else {
+            m
+        }
+    }
+
+    // self^exp mod m as a residue in [0, m), for either sign of self.
+    // None if m == 0.
+    pub fn mod_pow(&self, exp: &N, m: &N) -> Option<N> {
+        let r = self.abs_nat().mod_pow(exp, m)?;
+        if self.is_negative() && is_odd(exp) && !r.is_zero() {
+            Some(m.sub_unchecked_gte(&r))
+        } else {
+            Some(r)
+        }
+    }
+
+    // floor(sqrt(self)); None for negative self.
+    pub fn isqrt(&self) -> Option<Int<N>> {
+        self.nth_root(&N::from_usize(2))
+    }
+
+    // The integer n-th root, rounded toward zero. Negative self only has one
+    // for odd n (the cube root of -30 is -3). None if n == 0, or n is even and
+    // self is negative.
+    pub fn nth_root(&self, n: &N) -> Option<Int<N>> {
+        if self.is_negative() && !is_odd(n) {
+            return None;
+        }
+        let r = Int::from_nat(self.abs_nat().nth_root(n)?);
+        Some(if self.is_negative() { r.neg() } else { r })
+    }
+
+    // True if self = m^k for some integer m and some k >= 2. A negative value
+    // needs an odd k: -8 = (-2)^3 is one, -4 is not.
+    pub fn is_perfect_power(&self) -> bool {
+        if !self.is_negative() {
+            return self.abs_nat().is_perfect_power();
+        }
+        let a = self.abs_nat();
+        if a == N::one() {
+            return true;
+        }
+        let two = N::from_usize(2);
+        let mut k = N::from_usize(3);
+        while two.pow_at_most(&k, &a).is_some() {
+            let r = a.nth_root(&k).expect("k != 0");
+            if r.pow(&k) == a {
+                return true;
+            }
+            k = k.add(&two);
+        }
+        false
+    }
+
+    // floor(log_base(self)) for positive self. None if self <= 0 or base < 2.
+    pub fn ilog(&self, base: &N) -> Option<N> {
+        match self {
+            Int::Pos(n) => n.ilog(base),
+            _ => None,
+        }
+    }
+}
+
+fn is_odd<N: Natural>(n: &N) -> bool {
+    !n.div_mod(&N::from_usize(2)).expect("2 != 0").1.is_zero()
+}
+
+impl<N: Natural> PartialOrd for Int<N> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp_int(other))
+        Some(self.cmp(other))
     }
 }
 
-impl Ord for Int {
+impl<N: Natural> Ord for Int<N> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.cmp_int(other)
     }
 }
 
-impl fmt::Display for Int {
+impl<N: Natural> fmt::Display for Int<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Int::Zero => write!(f, "0"),
@@ -128,6 +212,33 @@ impl fmt::Display for Int {
     }
 }
 
+// Operators. `/` and `%` truncate toward zero (see `Rounding`) and panic on
+// a zero divisor; the other operators are total on Z.
+forward_binop!([N: Natural] Int<N>, Add::add, AddAssign::add_assign, |a, b| Int::add(a, b));
+forward_binop!([N: Natural] Int<N>, Sub::sub, SubAssign::sub_assign, |a, b| Int::sub(a, b));
+forward_binop!([N: Natural] Int<N>, Mul::mul, MulAssign::mul_assign, |a, b| Int::mul(a, b));
+forward_binop!([N: Natural] Int<N>, Div::div, DivAssign::div_assign, |a, b| {
+    Int::checked_div(a, b).expect("Int division by zero")
+});
+forward_binop!([N: Natural] Int<N>, Rem::rem, RemAssign::rem_assign, |a, b| {
+    Int::div_rem(a, b, Rounding::Trunc).expect("Int division by zero").1
+});
+forward_neg!([N: Natural] Int<N>, |a| Int::neg(a));
+forward_sum_product!([N: Natural] Int<N>, zero = Int::zero(), one = Int::from_nat(N::one()));
+
+// Test helpers shared by the Z submodules.
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::Int;
+    use crate::n::Nat;
+
+    // A small signed integer on the Peano backend.
+    pub(crate) fn int(v: isize) -> Int {
+        let m = Int::from_nat(Nat::from_usize(v.unsigned_abs()));
+        if v < 0 { m.neg() } else { m }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,4 +257,51 @@ mod tests {
         let four = Int::from_nat(Nat::from_usize(4));
         assert_eq!(format!("{}", neg_three.mul(&four)), "-12");
     }
+
+    #[test]
+    fn int_operators() {
+        let three = Int::from_nat(Nat::from_usize(3));
+        let five = Int::from_nat(Nat::from_usize(5));
+        assert_eq!(format!("{}", &three - &five), "-2");
+        assert_eq!(format!("{}", -&three * &five), "-15");
+
+        let mut acc = Int::zero();
+        acc -= &five;
+        acc += three;
+        assert_eq!(format!("{}", acc), "-2");
+
+        let xs = [-2isize, 3, -4].map(|v| {
+            let m = Int::from_nat(Nat::from_usize(v.unsigned_abs()));
+            if v < 0 { -m } else { m }
+        });
+        assert_eq!(format!("{}", xs.iter().sum::<Int>()), "-3");
+        assert_eq!(format!("{}", xs.iter().product::<Int>()), "24");
+    }
+
+    #[test]
+    fn int_powers_and_roots() {
+        use super::test_util::int;
+        let n = Nat::from_usize;
+
+        assert_eq!(int(-2).pow(&n(3)), int(-8));
+        assert_eq!(int(-2).pow(&n(4)), int(16));
+        assert_eq!(int(-3).pow(&n(0)), int(1));
+        assert_eq!(int(-2).mod_pow(&n(3), &n(5)), Some(n(2))); // -8 = 2 mod 5
+        assert_eq!(int(-5).mod_pow(&n(3), &n(5)), Some(n(0)));
+        assert_eq!(int(3).mod_pow(&n(3), &n(0)), None);
+
+        assert_eq!(int(17).isqrt(), Some(int(4)));
+        assert_eq!(int(-4).isqrt(), None);
+        assert_eq!(int(-30).nth_root(&n(3)), Some(int(-3)));
+        assert_eq!(int(-27).nth_root(&n(3)), Some(int(-3)));
+
+        assert!(int(-8).is_perfect_power());
+        assert!(int(-1).is_perfect_power());
+        assert!(!int(-4).is_perfect_power());
+        assert!(int(36).is_perfect_power());
+        assert!(!int(-6).is_perfect_power());
+
+        assert_eq!(int(81).ilog(&n(3)), Some(n(4)));
+        assert_eq!(int(-81).ilog(&n(3)), None);
+    }
 }
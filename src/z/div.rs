@@ -0,0 +1,251 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+use crate::n::Natural;
+
+use super::{Int, is_odd};
+
+// Integer division. For b != 0 every convention returns (q, r) with
+//   a = q*b + r,  |r| < |b|,
+// and they differ only in which way q is rounded when b does not divide a,
+// which fixes the sign of r:
+//
+//                     7 / 2    -7 / 2    7 / -2    -7 / -2
+//   Trunc             3 r  1   -3 r -1   -3 r  1    3 r -1   r has the sign of a
+//   Floor             3 r  1   -4 r  1   -4 r -1    3 r -1   r has the sign of b
+//   Ceil              4 r -1   -3 r -1   -3 r  1    4 r  1   r has the opposite sign of b
+//   Euclid            3 r  1   -4 r  1   -3 r  1    4 r  1   r >= 0
+//   HalfEven          4 r -1   -4 r  1   -4 r -1    4 r  1   |r| <= |b|/2
+//   HalfAwayFromZero  4 r -1   -4 r  1   -4 r -1    4 r  1   |r| <= |b|/2
+//   HalfUp            4 r -1   -3 r -1   -3 r  1    4 r  1   |r| <= |b|/2
+//
+// The last three round to the nearest quotient and only differ on ties
+// (5 / 2 is 2 under HalfEven, 3 under the other two).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    // Toward zero, like Rust's `/` and `%` on primitive integers.
+    Trunc,
+    // Toward negative infinity.
+    Floor,
+    // Toward positive infinity.
+    Ceil,
+    // Whichever way keeps the remainder nonnegative.
+    Euclid,
+    // To nearest; ties to the even quotient (banker's rounding).
+    HalfEven,
+    // To nearest; ties away from zero (school rounding).
+    HalfAwayFromZero,
+    // To nearest; ties toward positive infinity.
+    HalfUp,
+}
+
+// The error returned by `Int::checked_div` for a zero divisor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DivisionByZero;
+
+impl fmt::Display for DivisionByZero {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "division by zero")
+    }
+}
+
+impl Error for DivisionByZero {}
+
+impl<N: Natural> Int<N> {
+    // (quotient, remainder) under the given convention. None if other == 0.
+    pub fn div_rem(&self, other: &Int<N>, rounding: Rounding) -> Option<(Int<N>, Int<N>)> {
+        // Divide the magnitudes, then put the signs back: that is truncation.
+        let (q, r) = self.abs_nat().div_mod(&other.abs_nat())?;
+        let (q, r) = (Int::from_nat(q), Int::from_nat(r));
+        let q = if self.is_negative() != other.is_negative() {
+            q.neg()
+        } else {
+            q
+        };
+        let r = if self.is_negative() { r.neg() } else { r };
+        if r.is_zero() {
+            return Some((q, r));
+        }
+
+        // Exact division is the same in every convention; otherwise move q one
+        // step down (r += other) or up (r -= other) as the convention asks.
+        let exact_positive = self.is_negative() == other.is_negative();
+        let (away, up) = if exact_positive {
+            (Ordering::Greater, Ordering::Greater)
+        } else {
+            (Ordering::Less, Ordering::Equal)
+        };
+        let step = match rounding {
+            Rounding::Trunc => Ordering::Equal,
+            Rounding::Floor if r.is_negative() != other.is_negative() => Ordering::Less,
+            Rounding::Ceil if r.is_negative() == other.is_negative() => Ordering::Greater,
+            Rounding::Floor | Rounding::Ceil => Ordering::Equal,
+            Rounding::Euclid if r.is_negative() => {
+                if other.is_negative() {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                }
+            }
+            Rounding::Euclid => Ordering::Equal,
+            Rounding::HalfEven | Rounding::HalfAwayFromZero | Rounding::HalfUp => {
+                // Truncation is nearest unless 2|r| > |b|; on 2|r| = |b| it's a tie.
+                let twice = r.abs_nat().add(&r.abs_nat());
+                match twice.cmp(&other.abs_nat()) {
+                    Ordering::Less => Ordering::Equal,
+                    Ordering::Greater => away,
+                    Ordering::Equal => match rounding {
+                        Rounding::HalfEven if is_odd(&q.abs_nat()) => away,
+                        Rounding::HalfEven => Ordering::Equal,
+                        Rounding::HalfAwayFromZero => away,
+                        _ => up,
+                    },
+                }
+            }
+        };
+        let one = Int::from_nat(N::one());
+        Some(match step {
+            Ordering::Less => (q.sub(&one), r.add(other)),
+            Ordering::Greater => (q.add(&one), r.sub(other)),
+            Ordering::Equal => (q, r),
+        })
+    }
+
+    // The truncated quotient, or an error for a zero divisor.
+    pub fn checked_div(&self, other: &Int<N>) -> Result<Int<N>, DivisionByZero> {
+        self.div_rem(other, Rounding::Trunc)
+            .map(|(q, _)| q)
+            .ok_or(DivisionByZero)
+    }
+
+    // self mod m as a residue in [0, |m|), for either sign of self and m.
+    // None if m == 0.
+    pub fn rem_euclid(&self, m: &Int<N>) -> Option<N> {
+        let (_, r) = self.div_rem(m, Rounding::Euclid)?;
+        Some(r.abs_nat())
+    }
+
+    // -1, 0 or 1.
+    pub fn signum(&self) -> Int<N> {
+        match self {
+            Int::Neg(_) => Int::from_nat(N::one()).neg(),
+            Int::Zero => Int::Zero,
+            Int::Pos(_) => Int::from_nat(N::one()),
+        }
+    }
+
+    pub fn abs(&self) -> Int<N> {
+        Int::from_nat(self.abs_nat())
+    }
+
+    // `min`, `max` and `clamp` come from `Ord`.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::Nat;
+    use crate::z::test_util::int;
+
+    #[test]
+    fn conventions_on_every_sign() {
+        // The table at the top of this file.
+        let cases = [
+            (Rounding::Trunc, [(3, 1), (-3, -1), (-3, 1), (3, -1)]),
+            (Rounding::Floor, [(3, 1), (-4, 1), (-4, -1), (3, -1)]),
+            (Rounding::Ceil, [(4, -1), (-3, -1), (-3, 1), (4, 1)]),
+            (Rounding::Euclid, [(3, 1), (-4, 1), (-3, 1), (4, 1)]),
+            (Rounding::HalfEven, [(4, -1), (-4, 1), (-4, -1), (4, 1)]),
+            (
+                Rounding::HalfAwayFromZero,
+                [(4, -1), (-4, 1), (-4, -1), (4, 1)],
+            ),
+            (Rounding::HalfUp, [(4, -1), (-3, -1), (-3, 1), (4, 1)]),
+        ];
+        for (rounding, expected) in cases {
+            for ((a, b), (q, r)) in [(7, 2), (-7, 2), (7, -2), (-7, -2)]
+                .into_iter()
+                .zip(expected)
+            {
+                let got = int(a).div_rem(&int(b), rounding);
+                assert_eq!(got, Some((int(q), int(r))), "{:?} {} / {}", rounding, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn invariant_holds_and_exact_division_agrees() {
+        for rounding in [
+            Rounding::Trunc,
+            Rounding::Floor,
+            Rounding::Ceil,
+            Rounding::Euclid,
+            Rounding::HalfEven,
+            Rounding::HalfAwayFromZero,
+            Rounding::HalfUp,
+        ] {
+            for a in -9..=9 {
+                for b in [-4, -3, -1, 1, 3, 4] {
+                    let (q, r) = int(a).div_rem(&int(b), rounding).unwrap();
+                    assert_eq!(q.mul(&int(b)).add(&r), int(a));
+                    assert!(r.abs() < int(b).abs());
+                }
+                assert_eq!(int(a).div_rem(&int(0), rounding), None);
+            }
+            assert_eq!(int(-12).div_rem(&int(4), rounding), Some((int(-3), int(0))));
+        }
+    }
+
+    #[test]
+    fn nearest_modes_break_ties_differently() {
+        let q = |a, b, rounding| int(a).div_rem(&int(b), rounding).unwrap().0;
+        for (a, b, even, away, up) in [
+            (5, 2, 2, 3, 3),
+            (-5, 2, -2, -3, -2),
+            (7, 3, 2, 2, 2),
+            (8, 3, 3, 3, 3),
+        ] {
+            assert_eq!(q(a, b, Rounding::HalfEven), int(even), "{} / {}", a, b);
+            assert_eq!(
+                q(a, b, Rounding::HalfAwayFromZero),
+                int(away),
+                "{} / {}",
+                a,
+                b
+            );
+            assert_eq!(q(a, b, Rounding::HalfUp), int(up), "{} / {}", a, b);
+        }
+    }
+
+    #[test]
+    fn helpers() {
+        let n = Nat::from_usize;
+        assert_eq!(int(-7).rem_euclid(&int(3)), Some(n(2)));
+        assert_eq!(int(-7).rem_euclid(&int(-3)), Some(n(2)));
+        assert_eq!(int(7).rem_euclid(&int(0)), None);
+        assert_eq!(int(-7).checked_div(&int(2)), Ok(int(-3)));
+        assert_eq!(int(7).checked_div(&int(0)), Err(DivisionByZero));
+        assert_eq!(DivisionByZero.to_string(), "division by zero");
+        assert_eq!(int(-5).signum(), int(-1));
+        assert_eq!(int(0).signum(), int(0));
+        assert_eq!(int(-5).abs(), int(5));
+        assert_eq!(int(-5).min(int(2)), int(-5));
+        assert_eq!(int(-5).max(int(2)), int(2));
+    }
+
+    #[test]
+    fn operators_truncate() {
+        assert_eq!(int(-7) / int(2), int(-3));
+        assert_eq!(int(-7) % int(2), int(-1));
+        let mut x = int(20);
+        x /= &int(-6);
+        assert_eq!(x, int(-3));
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn operator_division_by_zero_panics() {
+        let _ = int(1) / int(0);
+    }
+}
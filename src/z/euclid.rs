@@ -0,0 +1,207 @@
+use crate::n::Natural;
+
+use super::Int;
+
+// Extended Euclid and what it unlocks: Bezout coefficients, modular inverses,
+// the Chinese Remainder Theorem and linear Diophantine equations.
+
+impl<N: Natural> Int<N> {
+    // Returns (g, x, y) with self*x + other*y = g, where g = gcd(|self|, |other|) >= 0.
+    // ext_gcd(0, 0) = (0, 0, 0).
+    pub fn ext_gcd(&self, other: &Int<N>) -> (Int<N>, Int<N>, Int<N>) {
+        if self.is_zero() && other.is_zero() {
+            return (Int::zero(), Int::zero(), Int::zero());
+        }
+        // Run Euclid on the magnitudes, carrying the coefficients along:
+        // at every step old_r = |a|*old_s + |b|*old_t.
+        let mut old_r = self.abs_nat();
+        let mut r = other.abs_nat();
+        let (mut old_s, mut s) = (Int::from_nat(N::one()), Int::zero());
+        let (mut old_t, mut t) = (Int::zero(), Int::from_nat(N::one()));
+        while !r.is_zero() {
+            let (q, rem) = old_r.div_mod(&r).expect("r != 0");
+            let q = Int::from_nat(q);
+            old_r = std::mem::replace(&mut r, rem);
+            let next_s = old_s.sub(&q.mul(&s));
+            old_s = std::mem::replace(&mut s, next_s);
+            let next_t = old_t.sub(&q.mul(&t));
+            old_t = std::mem::replace(&mut t, next_t);
+        }
+        // Put the signs of a and b back onto their coefficients.
+        let x = if self.is_negative() {
+            old_s.neg()
+        } else {
+            old_s
+        };
+        let y = if other.is_negative() {
+            old_t.neg()
+        } else {
+            old_t
+        };
+        (Int::from_nat(old_r), x, y)
+    }
+
+    // The inverse of self modulo m, in [0, m). None if m == 0 or self is not
+    // coprime to m.
+    pub fn mod_inverse(&self, m: &N) -> Option<N> {
+        if m.is_zero() {
+            return None;
+        }
+        let (g, x, _) = self.ext_gcd(&Int::from_nat(m.clone()));
+        if g != Int::from_nat(N::one()) {
+            return None;
+        }
+        Some(mod_floor(&x, m))
+    }
+}
+
+// Solve the system x = a_i (mod m_i). Moduli need not be coprime. Returns
+// (x, M) with M = lcm(m_i) and x the unique solution in [0, M), or None if a
+// modulus is 0 or the congruences contradict each other. An empty system is
+// solved by everything: (0, 1).
+pub fn crt<N: Natural>(congruences: &[(Int<N>, N)]) -> Option<(N, N)> {
+    let mut x = N::zero();
+    let mut m = N::one();
+    for (a, mi) in congruences {
+        if mi.is_zero() {
+            return None;
+        }
+        // Merge x (mod m) with a (mod mi): x + m*k = a (mod mi) needs
+        // g = gcd(m, mi) to divide a - x; then k = ((a - x)/g) * (m/g)^-1 mod mi/g.
+        let g = m.gcd(mi);
+        let diff = a.sub(&Int::from_nat(x.clone()));
+        let diff_g = div_exact_int(&diff, &g)?;
+        let mi_g = mi.div_exact(&g).expect("g divides mi");
+        let m_g = Int::from_nat(m.div_exact(&g).expect("g divides m"));
+        let inv = m_g.mod_inverse(&mi_g).expect("m/g and mi/g are coprime");
+        let k = mod_floor(&diff_g.mul(&Int::from_nat(inv)), &mi_g);
+        let lcm = m.mul(&mi_g);
+        x = x.add(&m.mul(&k)).div_mod(&lcm).expect("lcm != 0").1;
+        m = lcm;
+    }
+    Some((x, m))
+}
+
+// All integer solutions of a*x + b*y = c, as the one-parameter family
+//   x = x0 + k*dx,  y = y0 + k*dy   (k any integer)
+// with dx = b/g, dy = -a/g for g = gcd(a, b), and x0 reduced into [0, |dx|).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinearSolution<N> {
+    pub x0: Int<N>,
+    pub y0: Int<N>,
+    pub dx: Int<N>,
+    pub dy: Int<N>,
+}
+
+impl<N: Natural> LinearSolution<N> {
+    // The solution for parameter k.
+    pub fn at(&self, k: &Int<N>) -> (Int<N>, Int<N>) {
+        (self.x0.add(&k.mul(&self.dx)), self.y0.add(&k.mul(&self.dy)))
+    }
+}
+
+// None if there are no integer solutions (g does not divide c). Also None for
+// the degenerate a = b = 0, which is not a one-parameter family: either
+// nothing solves 0 = c, or (c = 0) every pair does.
+pub fn solve_linear_diophantine<N: Natural>(
+    a: &Int<N>,
+    b: &Int<N>,
+    c: &Int<N>,
+) -> Option<LinearSolution<N>> {
+    let (g, x, y) = a.ext_gcd(b);
+    if g.is_zero() {
+        return None;
+    }
+    let g_nat = g.abs_nat();
+    let scale = div_exact_int(c, &g_nat)?;
+    let dx = div_exact_int(b, &g_nat).expect("g divides b");
+    let dy = div_exact_int(a, &g_nat).expect("g divides a").neg();
+    let mut x0 = x.mul(&scale);
+    let mut y0 = y.mul(&scale);
+    if !dx.is_zero() {
+        // Slide along the family so x0 lands in [0, |dx|).
+        let reduced = Int::from_nat(mod_floor(&x0, &dx.abs_nat()));
+        let k = div_exact_int(&reduced.sub(&x0), &dx.abs_nat()).expect("same residue");
+        let k = if dx.is_negative() { k.neg() } else { k };
+        (x0, y0) = (x0.add(&k.mul(&dx)), y0.add(&k.mul(&dy)));
+    }
+    Some(LinearSolution { x0, y0, dx, dy })
+}
+
+// a mod m as a residue in [0, m), for either sign of a. m != 0.
+fn mod_floor<N: Natural>(a: &Int<N>, m: &N) -> N {
+    a.rem_euclid(&Int::from_nat(m.clone())).expect("m != 0")
+}
+
+// a / d when d divides a exactly, keeping the sign of a.
+fn div_exact_int<N: Natural>(a: &Int<N>, d: &N) -> Option<Int<N>> {
+    let q = Int::from_nat(a.abs_nat().div_exact(d)?);
+    Some(if a.is_negative() { q.neg() } else { q })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::n::Nat;
+    use crate::z::test_util::int;
+
+    #[test]
+    fn bezout_identity_holds_for_every_sign() {
+        for (a, b, g) in [
+            (240, 46, 2),
+            (-240, 46, 2),
+            (240, -46, 2),
+            (-7, -3, 1),
+            (0, 5, 5),
+        ] {
+            let (a, b) = (int(a), int(b));
+            let (gg, x, y) = a.ext_gcd(&b);
+            assert_eq!(gg, int(g));
+            assert_eq!(a.mul(&x).add(&b.mul(&y)), gg);
+        }
+        // gcd(0, 0) = 0 with both coefficients 0, not just any pair.
+        assert_eq!(int(0).ext_gcd(&int(0)), (int(0), int(0), int(0)));
+    }
+
+    #[test]
+    fn modular_inverse() {
+        let n = Nat::from_usize;
+        assert_eq!(int(3).mod_inverse(&n(11)), Some(n(4)));
+        assert_eq!(int(-3).mod_inverse(&n(11)), Some(n(7)));
+        assert_eq!(int(6).mod_inverse(&n(9)), None);
+        assert_eq!(int(5).mod_inverse(&n(1)), Some(n(0)));
+        assert_eq!(int(5).mod_inverse(&n(0)), None);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        let n = Nat::from_usize;
+        // Sunzi: x = 2 (mod 3), 3 (mod 5), 2 (mod 7) -> 23 (mod 105).
+        let sys = [(int(2), n(3)), (int(3), n(5)), (int(2), n(7))];
+        assert_eq!(crt(&sys), Some((n(23), n(105))));
+        // Non-coprime moduli that agree, and ones that don't.
+        assert_eq!(crt(&[(int(3), n(4)), (int(1), n(6))]), Some((n(7), n(12))));
+        assert_eq!(crt(&[(int(0), n(4)), (int(1), n(6))]), None);
+        assert_eq!(crt(&[(int(-1), n(5))]), Some((n(4), n(5))));
+        assert_eq!(crt::<Nat>(&[]), Some((n(0), n(1))));
+    }
+
+    #[test]
+    fn linear_diophantine_general_solution() {
+        // 6x + 9y = 21 reduces to 2x + 3y = 7; the smallest x0 >= 0 is 2.
+        let sol = solve_linear_diophantine(&int(6), &int(9), &int(21)).unwrap();
+        assert_eq!((sol.x0.clone(), sol.y0.clone()), (int(2), int(1)));
+        assert_eq!((sol.dx.clone(), sol.dy.clone()), (int(3), int(-2)));
+        for k in -3..=3 {
+            let (x, y) = sol.at(&int(k));
+            assert_eq!(int(6).mul(&x).add(&int(9).mul(&y)), int(21));
+        }
+
+        let sol = solve_linear_diophantine(&int(-4), &int(7), &int(5)).unwrap();
+        let (x, y) = sol.at(&int(2));
+        assert_eq!(int(-4).mul(&x).add(&int(7).mul(&y)), int(5));
+
+        assert!(solve_linear_diophantine(&int(6), &int(9), &int(20)).is_none());
+        assert!(solve_linear_diophantine(&int(0), &int(0), &int(0)).is_none());
+    }
+}
@@ -0,0 +1,142 @@
+use crate::n::{Nat, Natural};
+
+use super::Int;
+
+// Z as the Grothendieck group of N, without normalizing: a pair (a, b) of
+// naturals stands for a - b, and two pairs are the same integer when
+//   (a, b) ~ (c, d)  iff  a + d = b + c.
+// `Int` is the canonical form of each class (one side zero); `IntPair` keeps
+// whatever representative it was given, so the quotient construction stays
+// visible. `==` is the equivalence relation above, not field equality.
+#[derive(Clone, Debug)]
+pub struct IntPair<N = Nat> {
+    pub pos: N,
+    pub neg: N,
+}
+
+impl<N: Natural> IntPair<N> {
+    pub fn new(pos: N, neg: N) -> IntPair<N> {
+        IntPair { pos, neg }
+    }
+
+    // The canonical representative: (n, 0) or (0, n).
+    pub fn from_int(i: &Int<N>) -> IntPair<N> {
+        let (pos, neg) = i.as_diff();
+        IntPair { pos, neg }
+    }
+
+    pub fn to_int(&self) -> Int<N> {
+        Int::from_diff(self.pos.clone(), self.neg.clone())
+    }
+
+    // The same class with its canonical representative.
+    pub fn normalized(&self) -> IntPair<N> {
+        IntPair::from_int(&self.to_int())
+    }
+
+    // Each operation is defined on representatives. The tests check that the
+    // class of the result does not depend on which representatives we picked.
+
+    //   (a, b) + (c, d) = (a + c, b + d)
+    pub fn add(&self, other: &IntPair<N>) -> IntPair<N> {
+        IntPair::new(self.pos.add(&other.pos), self.neg.add(&other.neg))
+    }
+
+    //   -(a, b) = (b, a)
+    pub fn neg(&self) -> IntPair<N> {
+        IntPair::new(self.neg.clone(), self.pos.clone())
+    }
+
+    pub fn sub(&self, other: &IntPair<N>) -> IntPair<N> {
+        self.add(&other.neg())
+    }
+
+    //   (a, b) * (c, d) = (ac + bd, ad + bc),  from (a - b)(c - d)
+    pub fn mul(&self, other: &IntPair<N>) -> IntPair<N> {
+        let (a, b) = (&self.pos, &self.neg);
+        let (c, d) = (&other.pos, &other.neg);
+        IntPair::new(a.mul(c).add(&b.mul(d)), a.mul(d).add(&b.mul(c)))
+    }
+}
+
+impl<N: Natural> PartialEq for IntPair<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.pos.add(&other.neg) == self.neg.add(&other.pos)
+    }
+}
+
+impl<N: Natural> Eq for IntPair<N> {}
+
+forward_binop!([N: Natural] IntPair<N>, Add::add, AddAssign::add_assign, |a, b| IntPair::add(a, b));
+forward_binop!([N: Natural] IntPair<N>, Sub::sub, SubAssign::sub_assign, |a, b| IntPair::sub(a, b));
+forward_binop!([N: Natural] IntPair<N>, Mul::mul, MulAssign::mul_assign, |a, b| IntPair::mul(a, b));
+forward_neg!([N: Natural] IntPair<N>, |a| IntPair::neg(a));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(a: usize, b: usize) -> IntPair {
+        IntPair::new(Nat::from_usize(a), Nat::from_usize(b))
+    }
+
+    // A few representatives of each of -2, 0 and 3.
+    fn classes() -> Vec<Vec<IntPair>> {
+        vec![
+            vec![pair(0, 2), pair(1, 3), pair(5, 7)],
+            vec![pair(0, 0), pair(4, 4)],
+            vec![pair(3, 0), pair(4, 1), pair(9, 6)],
+        ]
+    }
+
+    #[test]
+    fn equality_is_the_equivalence_relation() {
+        let classes = classes();
+        for (i, ci) in classes.iter().enumerate() {
+            for (j, cj) in classes.iter().enumerate() {
+                for x in ci {
+                    for y in cj {
+                        assert_eq!(x == y, i == j, "{:?} {:?}", x, y);
+                    }
+                }
+            }
+        }
+        // Same class, different fields.
+        let (x, y) = (pair(1, 3), pair(5, 7));
+        assert_eq!(x, y);
+        assert_ne!(x.pos, y.pos);
+    }
+
+    #[test]
+    fn operations_are_well_defined_on_classes() {
+        let classes = classes();
+        for ci in &classes {
+            for cj in &classes {
+                // Any choice of representatives lands in the same class.
+                let (x0, y0) = (&ci[0], &cj[0]);
+                for x in ci {
+                    for y in cj {
+                        assert_eq!(x.add(y), x0.add(y0));
+                        assert_eq!(x.sub(y), x0.sub(y0));
+                        assert_eq!(x.mul(y), x0.mul(y0));
+                        assert_eq!(x.neg(), x0.neg());
+                        assert_eq!((x * y).to_int(), x0.to_int().mul(&y0.to_int()));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn converts_to_and_from_int() {
+        let p = pair(4, 9);
+        let i = p.to_int();
+        assert_eq!(i.to_string(), "-5");
+        assert_eq!(IntPair::from_int(&i), p);
+        let n = IntPair::from_int(&i);
+        assert_eq!((n.pos.to_usize(), n.neg.to_usize()), (0, 5));
+        let m = p.normalized();
+        assert_eq!((m.pos.to_usize(), m.neg.to_usize()), (0, 5));
+        assert_eq!(IntPair::from_int(&Int::<Nat>::zero()), pair(2, 2));
+    }
+}